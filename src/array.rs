@@ -0,0 +1,233 @@
+use super::object::{Coerce, Object, RuntimeType};
+use super::runtime_error::RuntimeError;
+
+/// The maximum number of elements, so that a huge array is an error instead of exhausting
+/// the memory.
+pub const MAX_LENGTH: usize = 1 << 22;
+
+/// An array with one or more dimensions, e.g. `Dim m(3, 4)` has the bounds `0 To 3` and
+/// `0 To 4`. An array declared with `As` converts the values stored into it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Array {
+  /// Lower and upper bound of each dimension, both inclusive.
+  bounds: Vec<(i32, i32)>,
+  element_type: Option<RuntimeType>,
+  /// Elements in row-major order, i.e. the last index changes fastest.
+  items: Vec<Object>,
+}
+
+impl Array {
+  /// Creates an array whose elements are the default value of `element_type`.
+  /// `Dim a()` has no dimensions and no elements until `ReDim`.
+  pub fn new(
+    bounds: Vec<(i32, i32)>,
+    element_type: Option<RuntimeType>,
+  ) -> Result<Array, RuntimeError> {
+    let initial = match element_type {
+      Some(t) => t.default_value(),
+      None => Object::Undefined,
+    };
+    let mut len: usize = if bounds.is_empty() { 0 } else { 1 };
+    for b in bounds.iter() {
+      len = match len.checked_mul(Self::length(b)) {
+        Some(l) if l <= MAX_LENGTH => l,
+        _ => return Err(RuntimeError::ArrayTooLarge(bounds)),
+      };
+    }
+    Ok(Array {
+      bounds,
+      element_type,
+      items: vec![initial; len],
+    })
+  }
+
+  /// An array without dimensions, e.g. `Dim a()` before `ReDim`.
+  pub fn empty(element_type: Option<RuntimeType>) -> Array {
+    Array {
+      bounds: vec![],
+      element_type,
+      items: vec![],
+    }
+  }
+
+  pub fn items(&self) -> &[Object] {
+    &self.items
+  }
+
+  pub fn get(&self, index: &[i32]) -> Result<Object, RuntimeError> {
+    let offset = self.offset(index)?;
+    Ok(self.items[offset].clone())
+  }
+
+  pub fn set(&mut self, index: &[i32], value: &Object) -> Result<(), RuntimeError> {
+    let offset = self.offset(index)?;
+    self.items[offset] = match &self.element_type {
+      Some(t) => value.coerce(t)?,
+      None => value.clone(),
+    };
+    Ok(())
+  }
+
+  /// Lower bound of the `dimension`, which counts from 1 as `LBound`.
+  pub fn lower_bound(&self, dimension: i32) -> Result<i32, RuntimeError> {
+    Ok(self.dimension(dimension)?.0)
+  }
+
+  /// Upper bound of the `dimension`, which counts from 1 as `UBound`.
+  pub fn upper_bound(&self, dimension: i32) -> Result<i32, RuntimeError> {
+    Ok(self.dimension(dimension)?.1)
+  }
+
+  /// Creates the array resized by `ReDim`. With `Preserve`, the elements whose index is
+  /// still in range keep their values, and only the last dimension can be resized as in VB.
+  pub fn redim(&self, bounds: Vec<(i32, i32)>, preserve: bool) -> Result<Array, RuntimeError> {
+    let mut resized = Array::new(bounds, self.element_type)?;
+    if !preserve || self.items.is_empty() {
+      return Ok(resized);
+    }
+    let last = self.bounds.len() - 1;
+    if self.bounds.len() != resized.bounds.len() || self.bounds[..last] != resized.bounds[..last] {
+      return Err(RuntimeError::IndexOutOfRange {
+        index: resized.bounds.iter().map(|b| b.1).collect(),
+        bounds: self.bounds.clone(),
+      });
+    }
+    for offset in 0..resized.items.len() {
+      let index = resized.index_of(offset);
+      if let Ok(o) = self.offset(&index) {
+        resized.items[offset] = self.items[o].clone();
+      }
+    }
+    Ok(resized)
+  }
+
+  fn length(bound: &(i32, i32)) -> usize {
+    (bound.1 as i64 - bound.0 as i64 + 1).max(0) as usize
+  }
+
+  fn dimension(&self, dimension: i32) -> Result<(i32, i32), RuntimeError> {
+    match self.bounds.get((dimension - 1) as usize) {
+      Some(b) if 1 <= dimension => Ok(*b),
+      _ => Err(RuntimeError::IndexOutOfRange {
+        index: vec![dimension],
+        bounds: vec![(1, self.bounds.len() as i32)],
+      }),
+    }
+  }
+
+  fn offset(&self, index: &[i32]) -> Result<usize, RuntimeError> {
+    let out_of_range = || RuntimeError::IndexOutOfRange {
+      index: index.to_vec(),
+      bounds: self.bounds.clone(),
+    };
+    if index.len() != self.bounds.len() {
+      return Err(out_of_range());
+    }
+    let mut offset = 0;
+    for (i, b) in index.iter().zip(self.bounds.iter()) {
+      if !(b.0..=b.1).contains(i) {
+        return Err(out_of_range());
+      }
+      offset = offset * Self::length(b) + (*i as i64 - b.0 as i64) as usize;
+    }
+    Ok(offset)
+  }
+
+  fn index_of(&self, offset: usize) -> Vec<i32> {
+    let mut index = vec![0; self.bounds.len()];
+    let mut rest = offset;
+    for (i, b) in self.bounds.iter().enumerate().rev() {
+      let len = Self::length(b);
+      index[i] = b.0 + (rest % len) as i32;
+      rest /= len;
+    }
+    index
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn elements_are_stored_in_row_major_order() {
+    let mut m = Array::new(vec![(1, 2), (0, 2)], Some(RuntimeType::Integer)).unwrap();
+    m.set(&[1, 2], &Object::Integer(3)).unwrap();
+    m.set(&[2, 0], &Object::Integer(4)).unwrap();
+    assert_eq!(m.get(&[1, 2]), Ok(Object::Integer(3)));
+    assert_eq!(m.items()[2], Object::Integer(3));
+    assert_eq!(m.items()[3], Object::Integer(4));
+    assert_eq!(m.get(&[1, 0]), Ok(Object::Integer(0)));
+  }
+
+  #[test]
+  fn index_out_of_bounds_is_an_error() {
+    let a = Array::new(vec![(0, 2)], None).unwrap();
+    let out_of_range = |index: Vec<i32>| RuntimeError::IndexOutOfRange {
+      index,
+      bounds: vec![(0, 2)],
+    };
+    assert_eq!(a.get(&[3]), Err(out_of_range(vec![3])));
+    assert_eq!(a.get(&[-1]), Err(out_of_range(vec![-1])));
+    assert_eq!(a.get(&[0, 0]), Err(out_of_range(vec![0, 0])));
+  }
+
+  #[test]
+  fn elements_are_converted_to_the_element_type() {
+    let mut a = Array::new(vec![(0, 1)], Some(RuntimeType::Double)).unwrap();
+    a.set(&[0], &Object::Integer(1)).unwrap();
+    assert_eq!(a.get(&[0]), Ok(Object::Double(1.0)));
+    assert_eq!(
+      a.set(&[1], &Object::String(String::from("x"))),
+      Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Double,
+        actual: RuntimeType::String,
+      })
+    );
+  }
+
+  #[test]
+  fn bounds_of_each_dimension() {
+    let m = Array::new(vec![(1, 3), (0, 4)], None).unwrap();
+    assert_eq!(m.lower_bound(1), Ok(1));
+    assert_eq!(m.upper_bound(2), Ok(4));
+    assert!(m.upper_bound(3).is_err());
+    assert!(m.lower_bound(0).is_err());
+  }
+
+  #[test]
+  fn huge_array_is_an_error() {
+    assert_eq!(
+      Array::new(vec![(0, i32::MAX)], None),
+      Err(RuntimeError::ArrayTooLarge(vec![(0, i32::MAX)]))
+    );
+    assert!(Array::new(vec![(0, 65535), (0, 65535)], None).is_err());
+    assert!(Array::new(vec![(i32::MIN, i32::MAX)], None).is_err());
+    assert!(Array::new(vec![(1, MAX_LENGTH as i32)], None).is_ok());
+  }
+
+  #[test]
+  fn redim_preserve_keeps_elements() {
+    let mut a = Array::new(vec![(0, 2)], None).unwrap();
+    a.set(&[1], &Object::Integer(1)).unwrap();
+    a.set(&[2], &Object::Integer(2)).unwrap();
+    let grown = a.redim(vec![(0, 4)], true).unwrap();
+    assert_eq!(grown.get(&[2]), Ok(Object::Integer(2)));
+    assert_eq!(grown.get(&[4]), Ok(Object::Undefined));
+    let shrunk = a.redim(vec![(0, 1)], true).unwrap();
+    assert_eq!(shrunk.items(), &[Object::Undefined, Object::Integer(1)]);
+    let reset = a.redim(vec![(0, 2)], false).unwrap();
+    assert_eq!(reset.get(&[1]), Ok(Object::Undefined));
+  }
+
+  #[test]
+  fn redim_preserve_resizes_only_the_last_dimension() {
+    let mut m = Array::new(vec![(0, 1), (0, 1)], None).unwrap();
+    m.set(&[1, 1], &Object::Integer(5)).unwrap();
+    let wider = m.redim(vec![(0, 1), (0, 3)], true).unwrap();
+    assert_eq!(wider.get(&[1, 1]), Ok(Object::Integer(5)));
+    assert!(m.redim(vec![(0, 2), (0, 1)], true).is_err());
+    assert!(m.redim(vec![(0, 1)], true).is_err());
+    assert!(m.redim(vec![(0, 2), (0, 1)], false).is_ok());
+  }
+}
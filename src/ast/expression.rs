@@ -1,15 +1,51 @@
+use super::super::currency::Currency;
+use super::super::date::Date;
 use super::operator::{BinaryOperator, UnaryOperator};
+use super::span::Span;
+use num_bigint::BigInt;
 use std::fmt;
 
+/// A part of an interpolated string such as `$"Total: {count * 2}"`.
 #[derive(Debug, PartialEq, Clone)]
-pub enum Expression {
+pub enum InterpolationPart {
+  Text(String),
+  Expression(Expression),
+}
+
+/// Arguments of a call. Named arguments such as `f(1, y:=2)` follow the positional ones.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Arguments {
+  pub positional: Vec<Expression>,
+  pub named: Vec<(String, Expression)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Expression {
+  pub kind: ExpressionKind,
+  pub span: Span,
+}
+
+impl Expression {
+  pub fn new(kind: ExpressionKind, span: Span) -> Self {
+    Expression { kind, span }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExpressionKind {
   Boolean(bool),
   Identifier(String),
   String(String),
   Integer(i32),
+  /// A decimal literal too large for an Integer.
+  BigInteger(BigInt),
+  Double(f64),
+  Currency(Currency),
+  Date(Date),
+  InterpolatedString(Vec<InterpolationPart>),
   FunctionInvocation {
     identifier: String,
-    arguments: Vec<Expression>,
+    arguments: Arguments,
   },
   Unary {
     operator: UnaryOperator,
@@ -23,21 +59,41 @@ pub enum Expression {
 }
 
 impl fmt::Display for Expression {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.kind)
+  }
+}
+
+impl fmt::Display for ExpressionKind {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
-      Expression::Boolean(b) => write!(f, "Expression::Boolean({})", b)?,
-      Expression::Identifier(i) => write!(f, "Expression::Identifier({})", i)?,
-      Expression::Integer(n) => write!(f, "Expression::Intger({})", n)?,
-      Expression::String(s) => write!(f, "Expression::String(\"{}\")", s)?,
-      Expression::FunctionInvocation {
+      ExpressionKind::Boolean(b) => write!(f, "Expression::Boolean({})", b)?,
+      ExpressionKind::Identifier(i) => write!(f, "Expression::Identifier({})", i)?,
+      ExpressionKind::Integer(n) => write!(f, "Expression::Intger({})", n)?,
+      ExpressionKind::BigInteger(n) => write!(f, "Expression::BigInteger({})", n)?,
+      ExpressionKind::Double(n) => write!(f, "Expression::Double({})", n)?,
+      ExpressionKind::Currency(n) => write!(f, "Expression::Currency({})", n)?,
+      ExpressionKind::Date(d) => write!(f, "Expression::Date(#{}#)", d)?,
+      ExpressionKind::String(s) => write!(f, "Expression::String(\"{}\")", s)?,
+      ExpressionKind::InterpolatedString(parts) => {
+        write!(f, "Expression::InterpolatedString(")?;
+        for p in parts {
+          match p {
+            InterpolationPart::Text(s) => write!(f, "\"{}\"", s)?,
+            InterpolationPart::Expression(e) => write!(f, "{{{}}}", e)?,
+          }
+        }
+        write!(f, ")")?
+      }
+      ExpressionKind::FunctionInvocation {
         identifier,
         arguments,
       } => writeln!(f, "{}({:?})", identifier, arguments)?,
-      Expression::Unary {
+      ExpressionKind::Unary {
         operator,
         expression,
       } => write!(f, "Expression::Unary ({} {})", operator, expression)?,
-      Expression::Binary {
+      ExpressionKind::Binary {
         left,
         operator,
         right,
@@ -1,16 +1,87 @@
+use super::super::object::RuntimeType;
+use super::expression::Expression;
+use super::span::Span;
 use super::statement::Statement;
 use std::fmt;
 
+/// `Function` returns a value through `Return` or `Ret`, while `Sub` returns nothing.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ProcedureKind {
+  Function,
+  Sub,
+}
+
+impl fmt::Display for ProcedureKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+/// How an argument is passed. Parameters are `ByVal` unless `ByRef` is given.
+/// A `ByRef` parameter writes its value back to the variable passed by the caller.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PassingMode {
+  ByVal,
+  ByRef,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+  pub name: String,
+  pub passing: PassingMode,
+  /// Declared by `As` or a type suffix. None is a Variant.
+  pub type_name: Option<RuntimeType>,
+  /// True for `Optional` parameters, which take `default` when the argument is omitted.
+  pub optional: bool,
+  pub default: Option<Expression>,
+  /// True for `ParamArray args()`, which takes the rest of the arguments as an array.
+  pub param_array: bool,
+}
+
+impl fmt::Display for Parameter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.optional {
+      write!(f, "Optional ")?;
+    }
+    if self.param_array {
+      write!(f, "ParamArray ")?;
+    }
+    write!(f, "{:?} {}", self.passing, self.name)?;
+    if let Some(t) = &self.type_name {
+      write!(f, " As {}", t)?;
+    }
+    if let Some(d) = &self.default {
+      write!(f, " = {}", d)?;
+    }
+    Ok(())
+  }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionDeclaration {
+  pub kind: ProcedureKind,
   pub identifier: String,
-  pub arguments: Vec<String>,
+  pub parameters: Vec<Parameter>,
+  /// Type of the value returned by a Function. None is a Variant, and always None for a Sub.
+  pub return_type: Option<RuntimeType>,
   pub statements: Vec<Statement>,
+  pub span: Span,
 }
 
 impl fmt::Display for FunctionDeclaration {
   fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-    writeln!(fmt, "Fn {} ({:?})", self.identifier, self.arguments)?;
+    let parameters: Vec<String> = self.parameters.iter().map(|p| p.to_string()).collect();
+    write!(
+      fmt,
+      "{} {} ({})",
+      self.kind,
+      self.identifier,
+      parameters.join(", ")
+    )?;
+    match &self.return_type {
+      Some(t) => writeln!(fmt, " As {}", t)?,
+      None => writeln!(fmt)?,
+    }
     for s in self.statements.iter() {
       writeln!(fmt, "{}", s)?;
     }
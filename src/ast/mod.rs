@@ -2,4 +2,5 @@ pub mod expression;
 pub mod function;
 pub mod operator;
 pub mod program;
+pub mod span;
 pub mod statement;
@@ -1,5 +1,7 @@
 use std::fmt;
 
+/// Operators are named in upper case as the tokens are.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum UnaryOperator {
   NEGATIVE,
@@ -12,15 +14,24 @@ impl fmt::Display for UnaryOperator {
   }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum BinaryOperator {
   ADD,
   SUB,
   MUL,
   DIV,
+  /// `\`, which divides after rounding both operands to integers.
+  INTDIV,
   MOD,
+  /// `&`, which converts both operands to strings.
+  CONCAT,
   AND,
+  /// `AndAlso`, which evaluates the right operand only if the left one is True.
+  ANDALSO,
   OR,
+  /// `OrElse`, which evaluates the right operand only if the left one is False.
+  ORELSE,
   XOR,
   EXPOTENTIAL,
   EQ,
@@ -30,6 +41,32 @@ pub enum BinaryOperator {
   LT,
   LE,
 }
+impl BinaryOperator {
+  /// The operator as written in the source, e.g. `Mod` for MOD.
+  pub fn symbol(&self) -> &'static str {
+    match self {
+      BinaryOperator::ADD => "+",
+      BinaryOperator::SUB => "-",
+      BinaryOperator::MUL => "*",
+      BinaryOperator::DIV => "/",
+      BinaryOperator::INTDIV => "\\",
+      BinaryOperator::MOD => "Mod",
+      BinaryOperator::CONCAT => "&",
+      BinaryOperator::AND => "And",
+      BinaryOperator::ANDALSO => "AndAlso",
+      BinaryOperator::OR => "Or",
+      BinaryOperator::ORELSE => "OrElse",
+      BinaryOperator::XOR => "Xor",
+      BinaryOperator::EXPOTENTIAL => "^",
+      BinaryOperator::EQ => "=",
+      BinaryOperator::NE => "<>",
+      BinaryOperator::GT => ">",
+      BinaryOperator::GE => ">=",
+      BinaryOperator::LT => "<",
+      BinaryOperator::LE => "<=",
+    }
+  }
+}
 impl fmt::Display for BinaryOperator {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     write!(f, "{:?}", self)
@@ -15,7 +15,14 @@ impl fmt::Display for Program {
     }
     let functions = &self.functions;
     for f in functions {
-      writeln!(fmt, "fn {} ({:?})", f.identifier, f.arguments)?;
+      let parameters: Vec<&str> = f.parameters.iter().map(|p| p.name.as_str()).collect();
+      writeln!(
+        fmt,
+        "{} {} ({})",
+        f.kind,
+        f.identifier,
+        parameters.join(", ")
+      )?;
     }
     Ok(())
   }
@@ -0,0 +1,39 @@
+use std::fmt;
+
+/// Location of a node in the source. Lines and columns start from 0 as in `Token`,
+/// and `end_line`/`end_column` point just past the last character of the node.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Span {
+  pub file_name: String,
+  pub line: usize,
+  pub column: usize,
+  pub end_line: usize,
+  pub end_column: usize,
+}
+
+impl Span {
+  /// The span from the start of `self` to the end of `other`.
+  pub fn to(&self, other: &Span) -> Span {
+    Span {
+      file_name: self.file_name.clone(),
+      line: self.line,
+      column: self.column,
+      end_line: other.end_line,
+      end_column: other.end_column,
+    }
+  }
+}
+
+impl fmt::Display for Span {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "line: {}, column: {} - line: {}, column: {}, at {}",
+      self.line + 1,
+      self.column + 1,
+      self.end_line + 1,
+      self.end_column + 1,
+      self.file_name
+    )
+  }
+}
@@ -1,67 +1,265 @@
-use super::expression::Expression;
+use super::super::object::RuntimeType;
+use super::expression::{Arguments, Expression};
+use super::operator::BinaryOperator;
+use super::span::Span;
 use std::fmt;
 
+/// A condition of a `Case` clause, which is compared with the value of `Select Case`.
 #[derive(Debug, PartialEq, Clone)]
-pub enum Statement {
+pub enum CaseCondition {
+  /// `Case 1`
+  Value(Expression),
+  /// `Case 3 To 9`
+  Range(Expression, Expression),
+  /// `Case Is > 10`
+  Comparison(BinaryOperator, Expression),
+}
+
+impl fmt::Display for CaseCondition {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      CaseCondition::Value(e) => write!(f, "{}", e),
+      CaseCondition::Range(from, to) => write!(f, "{} To {}", from, to),
+      CaseCondition::Comparison(operator, e) => write!(f, "Is {} {}", operator, e),
+    }
+  }
+}
+
+/// The condition of a `Do` loop. `While` continues while it is true and `Until` while it
+/// is false.
+#[derive(Debug, PartialEq, Clone)]
+pub enum LoopCondition {
+  While(Expression),
+  Until(Expression),
+}
+
+impl fmt::Display for LoopCondition {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LoopCondition::While(e) => write!(f, "While {}", e),
+      LoopCondition::Until(e) => write!(f, "Until {}", e),
+    }
+  }
+}
+
+/// Bounds of a dimension of an array, e.g. `10` or `1 To 10`. The lower bound is 0 if omitted.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayBound {
+  pub lower: Option<Expression>,
+  pub upper: Expression,
+}
+
+impl fmt::Display for ArrayBound {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.lower {
+      Some(lower) => write!(f, "{} To {}", lower, self.upper),
+      None => write!(f, "{}", self.upper),
+    }
+  }
+}
+
+fn join_bounds(bounds: &[ArrayBound]) -> String {
+  let bounds: Vec<String> = bounds.iter().map(|b| b.to_string()).collect();
+  bounds.join(", ")
+}
+
+/// The block which `Exit` leaves or `Continue` goes on with.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BlockKind {
+  For,
+  Do,
+  Function,
+  Sub,
+}
+
+impl fmt::Display for BlockKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Statement {
+  pub kind: StatementKind,
+  pub span: Span,
+}
+
+impl Statement {
+  pub fn new(kind: StatementKind, span: Span) -> Self {
+    Statement { kind, span }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum StatementKind {
+  /// `Dim x As Integer = 1`. Without `As`, `type_name` is None and the variable is a Variant.
+  /// Without an initializer, the variable starts with the default value of its type.
+  /// `Dim a(10) As Integer` has `dimensions` and `type_name` is the type of the elements.
   Declaration {
     identifier: String,
-    expression: Expression,
+    type_name: Option<RuntimeType>,
+    dimensions: Option<Vec<ArrayBound>>,
+    expression: Option<Expression>,
   },
   Assignment {
     identifier: String,
     expression: Expression,
   },
+  /// `a(i, j) = v`
+  IndexedAssignment {
+    identifier: String,
+    indices: Vec<Expression>,
+    expression: Expression,
+  },
+  /// `ReDim [Preserve] a(20)`
+  ReDimStatement {
+    identifier: String,
+    preserve: bool,
+    dimensions: Vec<ArrayBound>,
+  },
   MethodInvocation {
     identifier: String,
-    arguments: Vec<Expression>,
+    arguments: Arguments,
   },
   IfStatement {
     if_blocks: Vec<(Expression, Vec<Statement>)>,
     else_statements: Vec<Statement>,
   },
+  SelectStatement {
+    expression: Expression,
+    case_blocks: Vec<(Vec<CaseCondition>, Vec<Statement>)>,
+    else_statements: Vec<Statement>,
+  },
   ForStatement {
     loop_counter: String,
     loop_counter_from: Expression,
     loop_counter_to: Expression,
+    loop_counter_step: Option<Expression>,
     block: Vec<Statement>,
   },
+  ForEachStatement {
+    element: String,
+    collection: Expression,
+    block: Vec<Statement>,
+  },
+  /// `Do [While|Until c] ... Loop [While|Until c]`. `While c ... Wend` is a `Do While` loop.
+  DoStatement {
+    pre_condition: Option<LoopCondition>,
+    block: Vec<Statement>,
+    post_condition: Option<LoopCondition>,
+  },
+  /// `Return` without a value leaves a `Sub`, or a `Function` with the value of `Ret`.
   ReturnStatement {
-    expression: Expression,
+    expression: Option<Expression>,
   },
+  ExitStatement(BlockKind),
+  ContinueStatement(BlockKind),
   Empty,
 }
 impl fmt::Display for Statement {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.kind)
+  }
+}
+
+impl fmt::Display for StatementKind {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
-      Statement::Declaration {
+      StatementKind::Declaration {
         identifier,
+        type_name,
+        dimensions,
         expression,
-      } => writeln!(f, "Const {} = {}", identifier, expression)?,
-      Statement::Assignment {
+      } => {
+        write!(f, "Const {}", identifier)?;
+        if let Some(d) = dimensions {
+          write!(f, "({})", join_bounds(d))?;
+        }
+        if let Some(t) = type_name {
+          write!(f, " As {}", t)?;
+        }
+        match expression {
+          Some(e) => writeln!(f, " = {}", e)?,
+          None => writeln!(f)?,
+        }
+      }
+      StatementKind::Assignment {
         identifier,
         expression,
       } => writeln!(f, "{} = {}", identifier, expression)?,
-      Statement::MethodInvocation {
+      StatementKind::IndexedAssignment {
+        identifier,
+        indices,
+        expression,
+      } => {
+        let indices: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+        writeln!(f, "{}({}) = {}", identifier, indices.join(", "), expression)?
+      }
+      StatementKind::ReDimStatement {
+        identifier,
+        preserve,
+        dimensions,
+      } => {
+        write!(f, "ReDim ")?;
+        if *preserve {
+          write!(f, "Preserve ")?;
+        }
+        writeln!(f, "{}({})", identifier, join_bounds(dimensions))?
+      }
+      StatementKind::MethodInvocation {
         identifier,
         arguments,
       } => writeln!(f, "{}({:?})", identifier, arguments)?,
-      Statement::ForStatement {
+      StatementKind::ForStatement {
         loop_counter,
         loop_counter_from,
         loop_counter_to,
+        loop_counter_step,
         block,
       } => {
-        writeln!(
+        write!(
           f,
           "For {} = {} To {}",
           loop_counter, loop_counter_from, loop_counter_to
         )?;
+        match loop_counter_step {
+          Some(step) => writeln!(f, " Step {}", step)?,
+          None => writeln!(f)?,
+        }
         for s in block {
           writeln!(f, "{}", s)?;
         }
         writeln!(f, "Next")?;
       }
-      Statement::IfStatement {
+      StatementKind::ForEachStatement {
+        element,
+        collection,
+        block,
+      } => {
+        writeln!(f, "For Each {} In {}", element, collection)?;
+        for s in block {
+          writeln!(f, "{}", s)?;
+        }
+        writeln!(f, "Next")?;
+      }
+      StatementKind::DoStatement {
+        pre_condition,
+        block,
+        post_condition,
+      } => {
+        match pre_condition {
+          Some(c) => writeln!(f, "Do {}", c)?,
+          None => writeln!(f, "Do")?,
+        }
+        for s in block {
+          writeln!(f, "{}", s)?;
+        }
+        match post_condition {
+          Some(c) => writeln!(f, "Loop {}", c)?,
+          None => writeln!(f, "Loop")?,
+        }
+      }
+      StatementKind::IfStatement {
         if_blocks,
         else_statements,
       } => {
@@ -71,7 +269,7 @@ impl fmt::Display for Statement {
             writeln!(f, "{}", s)?;
           }
         }
-        if 0 < else_statements.len() {
+        if !else_statements.is_empty() {
           writeln!(f, "Else")?;
           for s in else_statements {
             writeln!(f, "{}", s)?;
@@ -79,8 +277,34 @@ impl fmt::Display for Statement {
         }
         writeln!(f, "End If")?;
       }
-      Statement::ReturnStatement{expression} => writeln!(f, "Return {}", expression)?,
-      Statement::Empty => write!(f, "<empty>")?,
+      StatementKind::SelectStatement {
+        expression,
+        case_blocks,
+        else_statements,
+      } => {
+        writeln!(f, "Select Case {}", expression)?;
+        for (conditions, statements) in case_blocks {
+          let conditions: Vec<String> = conditions.iter().map(|c| c.to_string()).collect();
+          writeln!(f, "Case {}", conditions.join(", "))?;
+          for s in statements {
+            writeln!(f, "{}", s)?;
+          }
+        }
+        if !else_statements.is_empty() {
+          writeln!(f, "Case Else")?;
+          for s in else_statements {
+            writeln!(f, "{}", s)?;
+          }
+        }
+        writeln!(f, "End Select")?;
+      }
+      StatementKind::ReturnStatement { expression } => match expression {
+        Some(e) => writeln!(f, "Return {}", e)?,
+        None => writeln!(f, "Return")?,
+      },
+      StatementKind::ExitStatement(block) => writeln!(f, "Exit {}", block)?,
+      StatementKind::ContinueStatement(block) => writeln!(f, "Continue {}", block)?,
+      StatementKind::Empty => write!(f, "<empty>")?,
     }
     Ok(())
   }
@@ -1,9 +1,18 @@
-use super::object::Object;
+use super::object::{Coerce, Object, RuntimeType};
 use super::runtime_error::RuntimeError;
 use std::collections::BTreeMap;
 use std::fmt;
 
-type Variables = BTreeMap<String, Object>;
+/// A variable keeps the spelling used at its declaration for messages,
+/// while `Variables` is keyed by the lowercased name so that lookup is case-insensitive.
+/// A variable declared with `As` keeps its type, and values assigned to it are converted to it.
+struct Variable {
+  name: String,
+  type_name: Option<RuntimeType>,
+  value: Object,
+}
+
+type Variables = BTreeMap<String, Variable>;
 
 pub struct Context {
   stack: Vec<Variables>,
@@ -11,8 +20,8 @@ pub struct Context {
 impl fmt::Display for Context {
   fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     for variables in self.stack.iter() {
-      for (name, value) in variables {
-        writeln!(fmt, "{}={}", name, value)?;
+      for variable in variables.values() {
+        writeln!(fmt, "{}={}", variable.name, variable.value)?;
       }
       writeln!(fmt, "----------------------------------------")?;
     }
@@ -22,9 +31,9 @@ impl fmt::Display for Context {
 
 impl Context {
   pub fn new_root() -> Context {
-    return Context {
+    Context {
       stack: vec![BTreeMap::new()],
-    };
+    }
   }
 
   pub fn new_stack(&mut self) {
@@ -38,37 +47,84 @@ impl Context {
     self.stack.pop();
   }
 
-  pub fn declare_variable(&mut self, name: &String, value: &Object) -> Result<(), RuntimeError> {
+  pub fn declare_variable(&mut self, name: &str, value: &Object) -> Result<(), RuntimeError> {
+    self.declare_typed_variable(name, &None, value)
+  }
+
+  /// Declares a variable of `type_name`, or a Variant which takes any value if it is None.
+  pub fn declare_typed_variable(
+    &mut self,
+    name: &str,
+    type_name: &Option<RuntimeType>,
+    value: &Object,
+  ) -> Result<(), RuntimeError> {
     let len = self.stack.len();
     let v = &mut self.stack[len - 1];
-    if v.contains_key(name) {
-      Err(RuntimeError::DuplicatedVariableDefinition(name.clone()))
+    let key = Self::key_of(name);
+    if let Some(declared) = v.get(&key) {
+      Err(RuntimeError::DuplicatedVariableDefinition(
+        declared.name.clone(),
+      ))
     } else {
-      v.insert(name.clone(), value.clone());
+      let value = match type_name {
+        Some(t) => value.coerce(t)?,
+        None => value.clone(),
+      };
+      v.insert(
+        key,
+        Variable {
+          name: name.to_string(),
+          type_name: *type_name,
+          value,
+        },
+      );
       Ok(())
     }
   }
 
-  pub fn set_variable(&mut self, name: &String, value: &Object) -> Result<(), RuntimeError> {
+  pub fn set_variable(&mut self, name: &str, value: &Object) -> Result<(), RuntimeError> {
+    let key = Self::key_of(name);
     for i in (0..self.stack.len()).rev() {
       let v = &mut self.stack[i];
-      if v.contains_key(name) {
-        v.insert(name.clone(), value.clone());
+      if let Some(variable) = v.get_mut(&key) {
+        variable.value = match &variable.type_name {
+          Some(t) => value.coerce(t)?,
+          None => value.clone(),
+        };
         return Ok(());
       }
     }
-    Err(RuntimeError::UndefinedVariable(name.clone()))
+    Err(RuntimeError::UndefinedVariable(name.to_string()))
   }
 
-  pub fn get_variable(&mut self, name: &String) -> Result<Object, RuntimeError> {
-    for i in (0..self.stack.len()).rev() {
-      let v = &mut self.stack[i];
-      if v.contains_key(name) {
-        if let Some(value) = v.get(name) {
-          return Ok(value.clone());
-        }
+  pub fn get_variable(&mut self, name: &str) -> Result<Object, RuntimeError> {
+    self.get_variable_ref(name).cloned()
+  }
+
+  /// Borrows the value of a variable, e.g. to read an element of an array without copying it.
+  pub fn get_variable_ref(&self, name: &str) -> Result<&Object, RuntimeError> {
+    let key = Self::key_of(name);
+    for v in self.stack.iter().rev() {
+      if let Some(variable) = v.get(&key) {
+        return Ok(&variable.value);
       }
     }
-    Err(RuntimeError::UndefinedVariable(name.clone()))
+    Err(RuntimeError::UndefinedVariable(name.to_string()))
+  }
+
+  /// Borrows the value of a variable to update it in place, e.g. to store an element of an
+  /// array. The type of the variable is not checked, so the value must keep its type.
+  pub fn get_variable_mut(&mut self, name: &str) -> Result<&mut Object, RuntimeError> {
+    let key = Self::key_of(name);
+    for v in self.stack.iter_mut().rev() {
+      if let Some(variable) = v.get_mut(&key) {
+        return Ok(&mut variable.value);
+      }
+    }
+    Err(RuntimeError::UndefinedVariable(name.to_string()))
+  }
+
+  fn key_of(name: &str) -> String {
+    name.to_lowercase()
   }
 }
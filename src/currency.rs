@@ -0,0 +1,212 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Number of units in 1, i.e. a Currency has 4 decimal places as in VB.
+const SCALE: i128 = 10_000;
+const DECIMAL_PLACES: u32 = 4;
+
+/// A fixed-point decimal number stored as an integer count of 1/10000, so `0.1@ + 0.2@` is
+/// exactly `0.3`. Results with more decimal places are rounded half to even.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd)]
+pub struct Currency {
+  units: i64,
+}
+
+impl From<i32> for Currency {
+  fn from(n: i32) -> Currency {
+    Currency {
+      units: n as i64 * SCALE as i64,
+    }
+  }
+}
+
+impl Currency {
+  pub fn from_integer(n: i64) -> Option<Currency> {
+    Self::from_units((n as i128).checked_mul(SCALE)?)
+  }
+
+  /// Converts a Double, rounding half to even at the 4th decimal place.
+  pub fn from_double(d: f64) -> Option<Currency> {
+    let units = (d * SCALE as f64).round_ties_even();
+    if !(i64::MIN as f64..i64::MAX as f64).contains(&units) {
+      return None;
+    }
+    Some(Currency {
+      units: units as i64,
+    })
+  }
+
+  /// Parses decimal digits such as `19.99` or `-0.5` exactly. Text with an exponent such as
+  /// `1E3` is read as a Double first.
+  pub fn parse(text: &str) -> Option<Currency> {
+    if text.contains(['e', 'E']) {
+      return Self::from_double(text.parse::<f64>().ok()?);
+    }
+    let (negative, text) = match text.strip_prefix('-') {
+      Some(t) => (true, t),
+      None => (false, text),
+    };
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let digits = format!("{}{}", integer, fraction);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+      return None;
+    }
+    let value = digits.parse::<i128>().ok()?.checked_mul(SCALE)?;
+    let units = round_div(value, 10i128.checked_pow(fraction.len() as u32)?);
+    Self::from_units(if negative { -units } else { units })
+  }
+
+  pub fn to_double(self) -> f64 {
+    self.units as f64 / SCALE as f64
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.units == 0
+  }
+
+  pub fn is_negative(&self) -> bool {
+    self.units < 0
+  }
+
+  pub fn checked_add(&self, other: &Currency) -> Option<Currency> {
+    Self::from_units(self.units as i128 + other.units as i128)
+  }
+
+  pub fn checked_sub(&self, other: &Currency) -> Option<Currency> {
+    Self::from_units(self.units as i128 - other.units as i128)
+  }
+
+  pub fn checked_mul(&self, other: &Currency) -> Option<Currency> {
+    Self::from_units(round_div(self.units as i128 * other.units as i128, SCALE))
+  }
+
+  pub fn checked_div(&self, other: &Currency) -> Option<Currency> {
+    if other.is_zero() {
+      return None;
+    }
+    Self::from_units(round_div(self.units as i128 * SCALE, other.units as i128))
+  }
+
+  /// The remainder, which has the sign of `self` as `Mod`.
+  pub fn checked_rem(&self, other: &Currency) -> Option<Currency> {
+    if other.is_zero() {
+      return None;
+    }
+    Self::from_units(self.units as i128 % other.units as i128)
+  }
+
+  /// Rounds half to even to `digits` decimal places, e.g. `2.345` to 2 places is `2.34`.
+  pub fn round(&self, digits: u32) -> Option<Currency> {
+    if DECIMAL_PLACES <= digits {
+      return Some(*self);
+    }
+    let unit = 10i128.pow(DECIMAL_PLACES - digits);
+    Self::from_units(round_div(self.units as i128, unit) * unit)
+  }
+
+  fn from_units(units: i128) -> Option<Currency> {
+    i64::try_from(units).ok().map(|units| Currency { units })
+  }
+}
+
+/// Divides `n` by `d`, rounding half to even.
+fn round_div(n: i128, d: i128) -> i128 {
+  let quotient = n / d;
+  let twice_remainder = (n % d).abs() * 2;
+  let round_up = twice_remainder > d.abs() || (twice_remainder == d.abs() && quotient % 2 != 0);
+  match (round_up, (n < 0) != (d < 0)) {
+    (true, true) => quotient - 1,
+    (true, false) => quotient + 1,
+    _ => quotient,
+  }
+}
+
+impl fmt::Display for Currency {
+  /// Prints the exact value without trailing zeros, e.g. `1.5` or `-0.05`.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let sign = if self.is_negative() { "-" } else { "" };
+    let units = self.units.unsigned_abs();
+    let integer = units / SCALE as u64;
+    let fraction = units % SCALE as u64;
+    if fraction == 0 {
+      return write!(f, "{}{}", sign, integer);
+    }
+    let fraction = format!("{:0width$}", fraction, width = DECIMAL_PLACES as usize);
+    write!(f, "{}{}.{}", sign, integer, fraction.trim_end_matches('0'))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn currency(text: &str) -> Currency {
+    Currency::parse(text).unwrap()
+  }
+
+  #[test]
+  fn parse_is_exact_and_rounds_half_to_even() {
+    assert_eq!(currency("19.99").units, 199_900);
+    assert_eq!(currency("-0.5").units, -5_000);
+    assert_eq!(currency("1E3").units, 10_000_000);
+    assert_eq!(currency("0.00005").units, 0);
+    assert_eq!(currency("0.00015").units, 2);
+    assert_eq!(currency("-0.00025").units, -2);
+    assert_eq!(Currency::parse("1.2.3"), None);
+    assert_eq!(Currency::parse(""), None);
+    assert_eq!(Currency::parse("99999999999999999999"), None);
+  }
+
+  #[test]
+  fn arithmetic_is_exact() {
+    let sum = currency("0.1").checked_add(&currency("0.2")).unwrap();
+    assert_eq!(sum, currency("0.3"));
+    assert_eq!(
+      currency("10").checked_div(&Currency::from(3)),
+      Some(currency("3.3333"))
+    );
+    assert_eq!(
+      currency("1.25").checked_mul(&currency("0.0002")),
+      Some(currency("0.0002"))
+    );
+    assert_eq!(
+      currency("-7").checked_rem(&Currency::from(3)),
+      Some(currency("-1"))
+    );
+    assert_eq!(currency("1").checked_div(&Currency::from(0)), None);
+    let max = Currency { units: i64::MAX };
+    assert_eq!(max.checked_add(&currency("0.0001")), None);
+    assert_eq!(max.checked_mul(&Currency::from(2)), None);
+  }
+
+  #[test]
+  fn round_is_bankers_rounding() {
+    let round = |text: &str, digits: u32| currency(text).round(digits).unwrap().to_string();
+    assert_eq!(round("2.5", 0), "2");
+    assert_eq!(round("3.5", 0), "4");
+    assert_eq!(round("-2.5", 0), "-2");
+    assert_eq!(round("0.125", 2), "0.12");
+    assert_eq!(round("0.135", 2), "0.14");
+    assert_eq!(round("0.1251", 2), "0.13");
+    assert_eq!(round("1.2345", 4), "1.2345");
+    assert_eq!(
+      Currency::from_double(0.5)
+        .unwrap()
+        .round(0)
+        .unwrap()
+        .to_string(),
+      "0"
+    );
+  }
+
+  #[test]
+  fn display_trims_trailing_zeros() {
+    assert_eq!(currency("1.5000").to_string(), "1.5");
+    assert_eq!(currency("-0.05").to_string(), "-0.05");
+    assert_eq!(currency("42").to_string(), "42");
+    assert_eq!(
+      Currency { units: i64::MIN }.to_string(),
+      "-922337203685477.5808"
+    );
+  }
+}
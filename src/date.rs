@@ -0,0 +1,296 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// A date and time with second precision, stored as seconds since 1970-01-01 00:00:00.
+/// Time zones are not supported, so every Date is in UTC as `Now` is.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd)]
+pub struct Date {
+  seconds: i64,
+}
+
+impl Date {
+  pub fn new(
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+  ) -> Option<Date> {
+    if !(1..=9999).contains(&year)
+      || !(1..=12).contains(&month)
+      || !(1..=days_in_month(year, month)).contains(&day)
+      || !(0..24).contains(&hour)
+      || !(0..60).contains(&minute)
+      || !(0..60).contains(&second)
+    {
+      return None;
+    }
+    let days = days_from_civil(year, month, day);
+    Some(Date {
+      seconds: days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second,
+    })
+  }
+
+  /// Parses the text of a `#...#` literal, e.g. `2026-10-18`, `2026/10/18`,
+  /// `2026-10-18 12:30:00` or `10/18/2026` with the month first.
+  pub fn parse(text: &str) -> Option<Date> {
+    let mut parts = text.split_whitespace();
+    let date: Vec<&str> = parts.next()?.split(['-', '/']).collect();
+    let time: Vec<&str> = match parts.next() {
+      Some(t) => t.split(':').collect(),
+      None => vec![],
+    };
+    if parts.next().is_some() || date.len() != 3 || time.len() == 1 || 3 < time.len() {
+      return None;
+    }
+    let (year, month, day) = match date[..] {
+      [m, d, y] if m.len() <= 2 && 2 < y.len() => (y, m, d),
+      [y, m, d] => (y, m, d),
+      _ => return None,
+    };
+    let number = |s: Option<&&str>| -> Option<i64> {
+      match s {
+        Some(s) => s.parse::<i64>().ok(),
+        None => Some(0),
+      }
+    };
+    Date::new(
+      year.parse::<i64>().ok()?,
+      month.parse::<i64>().ok()?,
+      day.parse::<i64>().ok()?,
+      number(time.first())?,
+      number(time.get(1))?,
+      number(time.get(2))?,
+    )
+  }
+
+  fn days(&self) -> i64 {
+    self.seconds.div_euclid(SECONDS_PER_DAY)
+  }
+
+  fn seconds_of_day(&self) -> i64 {
+    self.seconds.rem_euclid(SECONDS_PER_DAY)
+  }
+
+  pub fn year(&self) -> i64 {
+    civil_from_days(self.days()).0
+  }
+
+  pub fn month(&self) -> i64 {
+    civil_from_days(self.days()).1
+  }
+
+  pub fn day(&self) -> i64 {
+    civil_from_days(self.days()).2
+  }
+
+  /// Adds `days` days, or returns `None` if the result is out of the years 1 to 9999.
+  pub fn add_days(&self, days: i64) -> Option<Date> {
+    Date::checked(self.seconds + days * SECONDS_PER_DAY)
+  }
+
+  fn checked(seconds: i64) -> Option<Date> {
+    let date = Date { seconds };
+    if (1..=9999).contains(&date.year()) {
+      Some(date)
+    } else {
+      None
+    }
+  }
+
+  /// Number of days from `other` to `self`, including the fraction of a day.
+  pub fn days_since(&self, other: &Date) -> f64 {
+    (self.seconds - other.seconds) as f64 / SECONDS_PER_DAY as f64
+  }
+
+  /// Adds `number` intervals as VBA's `DateAdd`. Adding months keeps the day when possible,
+  /// otherwise it becomes the last day of the month, e.g. Jan 31 + 1 month is Feb 28.
+  /// Returns `None` for an unknown interval, and `Some(None)` if the result is out of range.
+  pub fn add_interval(&self, interval: &str, number: i64) -> Option<Option<Date>> {
+    let seconds = match interval.to_lowercase().as_str() {
+      "yyyy" => return Some(self.add_months(number * 12)),
+      "q" => return Some(self.add_months(number * 3)),
+      "m" => return Some(self.add_months(number)),
+      "ww" => number * 7 * SECONDS_PER_DAY,
+      "d" | "y" | "w" => number * SECONDS_PER_DAY,
+      "h" => number * 3600,
+      "n" => number * 60,
+      "s" => number,
+      _ => return None,
+    };
+    Some(Date::checked(self.seconds + seconds))
+  }
+
+  fn add_months(&self, months: i64) -> Option<Date> {
+    let (year, month, day) = civil_from_days(self.days());
+    let total = year * 12 + (month - 1) + months;
+    let year = total.div_euclid(12);
+    let month = total.rem_euclid(12) + 1;
+    let day = day.min(days_in_month(year, month));
+    Date::checked(days_from_civil(year, month, day) * SECONDS_PER_DAY + self.seconds_of_day())
+  }
+
+  /// Counts the interval boundaries crossed from `self` to `other` as VBA's `DateDiff`.
+  pub fn diff(&self, interval: &str, other: &Date) -> Option<i64> {
+    let (y1, m1, _) = civil_from_days(self.days());
+    let (y2, m2, _) = civil_from_days(other.days());
+    let boundaries = |unit: i64| other.seconds.div_euclid(unit) - self.seconds.div_euclid(unit);
+    match interval.to_lowercase().as_str() {
+      "yyyy" => Some(y2 - y1),
+      "q" => Some((y2 * 4 + (m2 - 1) / 3) - (y1 * 4 + (m1 - 1) / 3)),
+      "m" => Some((y2 * 12 + m2) - (y1 * 12 + m1)),
+      "d" | "y" => Some(other.days() - self.days()),
+      "w" => Some((other.days() - self.days()) / 7),
+      "ww" => Some(
+        // 1970-01-01 is a Thursday, so shift by 4 days to count Sundays.
+        (other.days() + 4).div_euclid(7) - (self.days() + 4).div_euclid(7),
+      ),
+      "h" => Some(boundaries(3600)),
+      "n" => Some(boundaries(60)),
+      "s" => Some(other.seconds - self.seconds),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for Date {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let (year, month, day) = civil_from_days(self.days());
+    write!(f, "{:04}-{:02}-{:02}", year, month, day)?;
+    let s = self.seconds_of_day();
+    if s != 0 {
+      write!(f, " {:02}:{:02}:{:02}", s / 3600, s / 60 % 60, s % 60)?;
+    }
+    Ok(())
+  }
+}
+
+/// Source of the current time for `Now`. Inject a `FixedClock` to make scripts deterministic.
+pub trait Clock {
+  fn now(&self) -> Date;
+}
+
+/// Reports the current time in UTC.
+pub struct SystemClock;
+impl Clock for SystemClock {
+  fn now(&self) -> Date {
+    let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+      Ok(d) => d.as_secs() as i64,
+      Err(e) => -(e.duration().as_secs() as i64),
+    };
+    Date { seconds }
+  }
+}
+
+pub struct FixedClock(pub Date);
+impl Clock for FixedClock {
+  fn now(&self) -> Date {
+    self.0
+  }
+}
+
+fn is_leap_year(year: i64) -> bool {
+  year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+  match month {
+    2 if is_leap_year(year) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar.
+/// See http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  let y = if month <= 2 { year - 1 } else { year };
+  let era = y.div_euclid(400);
+  let yoe = y - era * 400;
+  let mp = (month + 9) % 12;
+  let doy = (153 * mp + 2) / 5 + day - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+  let z = days + 719468;
+  let era = z.div_euclid(146097);
+  let doe = z - era * 146097;
+  let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+  (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn date(text: &str) -> Date {
+    Date::parse(text).unwrap()
+  }
+
+  #[test]
+  fn parse_accepts_year_first_and_month_first() {
+    let expected = Date::new(2026, 10, 18, 0, 0, 0).unwrap();
+    assert_eq!(date("2026-10-18"), expected);
+    assert_eq!(date("2026/10/18"), expected);
+    assert_eq!(date("10/18/2026"), expected);
+    assert_eq!(date("2026-10-18 12:30:00").to_string(), "2026-10-18 12:30:00");
+    assert_eq!(Date::parse("18/10/2026"), None);
+    assert_eq!(Date::parse("2026-02-29"), None);
+    assert_eq!(Date::parse("99999999999-01-01"), None);
+  }
+
+  #[test]
+  fn civil_dates_round_trip() {
+    assert_eq!(date("1970-01-01").seconds, 0);
+    assert_eq!(date("1899-12-30").to_string(), "1899-12-30");
+    let d = date("2024-02-29 23:59:59");
+    assert_eq!((d.year(), d.month(), d.day()), (2024, 2, 29));
+  }
+
+  #[test]
+  fn add_interval_clamps_the_day_to_the_month() {
+    let d = date("2026-01-31");
+    assert_eq!(d.add_interval("m", 1), Some(Some(date("2026-02-28"))));
+    assert_eq!(d.add_interval("yyyy", -2), Some(Some(date("2024-01-31"))));
+    assert_eq!(
+      d.add_interval("h", 25),
+      Some(Some(date("2026-02-01 01:00:00")))
+    );
+    assert_eq!(d.add_interval("x", 1), None);
+  }
+
+  #[test]
+  fn diff_counts_boundaries() {
+    let l = date("2026-12-31 23:59:59");
+    let r = date("2027-01-01");
+    assert_eq!(l.diff("yyyy", &r), Some(1));
+    assert_eq!(l.diff("d", &r), Some(1));
+    assert_eq!(l.diff("s", &r), Some(1));
+    // 2026-10-18 is a Sunday.
+    assert_eq!(date("2026-10-17").diff("ww", &date("2026-10-18")), Some(1));
+    assert_eq!(r.days_since(&l), 1.0 / 86400.0);
+  }
+
+  #[test]
+  fn results_out_of_the_year_range_are_none() {
+    let last = date("9999-12-31 23:59:59");
+    let first = date("0001-01-01");
+    assert_eq!(last.add_days(0), Some(last));
+    assert_eq!(last.add_days(1), None);
+    assert_eq!(first.add_days(-1), None);
+    assert_eq!(last.add_interval("s", 1), Some(None));
+    assert_eq!(first.add_interval("m", -1), Some(None));
+    assert_eq!(date("2026-10-18").add_interval("yyyy", -10000), Some(None));
+  }
+}
@@ -1,24 +1,58 @@
+use super::array::Array;
 use super::ast::{
-  expression::Expression, operator::BinaryOperator, operator::UnaryOperator, program::Program,
-  statement::Statement,
+  expression::Arguments, expression::Expression, expression::ExpressionKind,
+  expression::InterpolationPart, function::FunctionDeclaration, function::PassingMode,
+  function::ProcedureKind, operator::BinaryOperator, operator::UnaryOperator, program::Program,
+  statement::ArrayBound, statement::BlockKind, statement::CaseCondition, statement::LoopCondition,
+  statement::Statement, statement::StatementKind,
 };
 use super::context::Context;
-use super::object::{Add, Object, RuntimeType, TypeOf};
+use super::currency::Currency;
+use super::date::{Clock, Date, SystemClock};
+use super::object::{
+  integer, to_double, Add, Coerce, Iterate, Object, RuntimeType, ToText, TypeOf,
+};
 use super::runtime_error::RuntimeError;
 use log::debug;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+use std::convert::TryFrom;
 use std::rc::Rc;
 
+/// A jump out of the statements being executed, raised by `Return`, `Exit` and `Continue`.
+/// The enclosing loop or function call consumes it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Signal {
+  Return,
+  Exit(BlockKind),
+  Continue(BlockKind),
+}
+
+/// A variable or an array element passed to a `ByRef` parameter, which the value of the
+/// parameter is written back to after the call.
+enum Reference {
+  Variable(String),
+  Element(String, Vec<i32>),
+}
+
 pub struct Executor {
   context: Context,
-  to_return: bool,
+  signal: Option<Signal>,
+  clock: Box<dyn Clock>,
 }
 
 impl Executor {
   pub fn new() -> Self {
-    return Executor {
+    Self::with_clock(Box::new(SystemClock))
+  }
+
+  /// Creates an executor whose `Now` reads the given clock.
+  pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+    Executor {
       context: Context::new_root(),
-      to_return: false,
-    };
+      signal: None,
+      clock,
+    }
   }
 
   pub fn execute(&mut self, program: &Program) -> Result<Object, RuntimeError> {
@@ -26,13 +60,17 @@ impl Executor {
     for f in program.functions.iter() {
       self
         .context
-        .declare_variable(&f.identifier, &Object::Function(Rc::new(f.clone())))?;
+        .declare_variable(&f.identifier, &Object::Function(Rc::new(f.clone())))
+        .map_err(|e| e.locate(&f.span))?;
     }
 
     let mut r = Object::Undefined;
     for s in program.statements.iter() {
       r = self.execute_statement(s)?;
       debug!("Statement: {}", r);
+      if self.signal.is_some() {
+        break;
+      }
     }
     debug!("[Executor] <<<execute: {}", r);
     Ok(r)
@@ -41,72 +79,163 @@ impl Executor {
   fn execute_statements(&mut self, statements: &Vec<Statement>) -> Result<Object, RuntimeError> {
     debug!("[Executor] >>>execute_statements");
     for s in statements {
-      if self.to_return {
+      self.execute_statement(s)?;
+      if self.signal.is_some() {
         break;
       }
-      self.execute_statement(s)?;
     }
     debug!("[Executor] <<<execute_statements");
     Ok(Object::Undefined)
   }
 
   fn execute_statement(&mut self, statement: &Statement) -> Result<Object, RuntimeError> {
+    self
+      .execute_statement_kind(&statement.kind)
+      .map_err(|e| e.locate(&statement.span))
+  }
+
+  fn execute_statement_kind(&mut self, statement: &StatementKind) -> Result<Object, RuntimeError> {
     match statement {
-      Statement::Declaration {
+      StatementKind::Declaration {
+        identifier,
+        type_name,
+        dimensions: None,
+        expression,
+      } => self.execute_const_assignment(identifier, type_name, expression),
+      StatementKind::Declaration {
+        identifier,
+        type_name,
+        dimensions: Some(dimensions),
+        ..
+      } => {
+        let bounds = self.execute_array_bounds(dimensions)?;
+        let array = Object::Array(Array::new(bounds, *type_name)?);
+        self
+          .context
+          .declare_typed_variable(identifier, &Some(RuntimeType::Array), &array)?;
+        Ok(Object::Undefined)
+      }
+      StatementKind::IndexedAssignment {
         identifier,
+        indices,
         expression,
-      } => self.execute_const_assignment(identifier, &expression),
-      Statement::Assignment {
+      } => {
+        let index = self.execute_indices(indices)?;
+        let evaluated = self.execute_expression(expression)?;
+        self.write_element(identifier, &index, &evaluated)?;
+        Ok(evaluated)
+      }
+      StatementKind::ReDimStatement {
+        identifier,
+        preserve,
+        dimensions,
+      } => {
+        let bounds = self.execute_array_bounds(dimensions)?;
+        // `ReDim` also makes an array of a Variant.
+        let resized = match self.context.get_variable_ref(identifier)? {
+          Object::Array(a) => a.redim(bounds, *preserve)?,
+          _ => Array::new(bounds, None)?,
+        };
+        self
+          .context
+          .set_variable(identifier, &Object::Array(resized))?;
+        Ok(Object::Undefined)
+      }
+      StatementKind::Assignment {
         identifier,
         expression,
-      } => self.execute_assignment(identifier, &expression),
-      Statement::MethodInvocation {
+      } => self.execute_assignment(identifier, expression),
+      StatementKind::MethodInvocation {
         identifier,
         arguments,
-      } => self.execute_method(identifier, &arguments),
-      Statement::ForStatement {
+      } => self.execute_method(identifier, arguments),
+      StatementKind::ForStatement {
         loop_counter,
         loop_counter_from,
         loop_counter_to,
+        loop_counter_step,
         block,
       } => {
-        let mut counter = self.execute_expression(loop_counter_from)?;
-        self.context.declare_variable(loop_counter, &counter)?;
+        // The range and the step are evaluated once before the loop as in VB.
+        let mut counter = self.execute_number(loop_counter_from)?;
+        let to_value = self.execute_number(loop_counter_to)?;
+        let step = match loop_counter_step {
+          Some(e) => self.execute_number(e)?,
+          None => Object::Integer(1),
+        };
+        let exit_operator = match step {
+          Object::Integer(n) if n < 0 => BinaryOperator::LT,
+          Object::BigInteger(ref n) if n.is_negative() => BinaryOperator::LT,
+          Object::Double(n) if n < 0.0 => BinaryOperator::LT,
+          Object::Currency(n) if n.is_negative() => BinaryOperator::LT,
+          _ => BinaryOperator::GT,
+        };
+        self.bind_loop_variable(loop_counter, &counter)?;
         loop {
-          let to_value = self.execute_expression(loop_counter_to)?;
-          let exit = match (counter, to_value) {
-            (Object::Integer(l), Object::Integer(r)) => r < l,
-            _ => {
-              return Err(RuntimeError::TypeMismatch {
-                expected: RuntimeType::Integer,
-                actual: RuntimeType::Integer, // to_value.type_of(),
-              });
-            }
-          };
-          if exit {
+          let exit = Self::execute_binary_operation(counter, &exit_operator, to_value.clone())?;
+          if exit == Object::Boolean(true) {
             break;
           }
           self.execute_statements(block)?;
+          if self.end_of_iteration(BlockKind::For) {
+            break;
+          }
 
           counter = self.context.get_variable(loop_counter)?;
-          counter = counter.add(1)?;
+          counter = counter.add(&step)?;
           self.context.set_variable(loop_counter, &counter)?;
         }
-        return Ok(Object::Undefined);
+        Ok(Object::Undefined)
+      }
+      StatementKind::ForEachStatement {
+        element,
+        collection,
+        block,
+      } => {
+        let elements = self
+          .execute_expression(collection)?
+          .iterate()
+          .map_err(|e| e.locate(&collection.span))?;
+        for e in elements {
+          self.bind_loop_variable(element, &e)?;
+          self.execute_statements(block)?;
+          if self.end_of_iteration(BlockKind::For) {
+            break;
+          }
+        }
+        Ok(Object::Undefined)
+      }
+      StatementKind::DoStatement {
+        pre_condition,
+        block,
+        post_condition,
+      } => {
+        loop {
+          if let Some(c) = pre_condition {
+            if !self.test_loop_condition(c)? {
+              break;
+            }
+          }
+          self.execute_statements(block)?;
+          if self.end_of_iteration(BlockKind::Do) {
+            break;
+          }
+          if let Some(c) = post_condition {
+            if !self.test_loop_condition(c)? {
+              break;
+            }
+          }
+        }
+        Ok(Object::Undefined)
       }
-      Statement::IfStatement {
+      StatementKind::IfStatement {
         if_blocks,
         else_statements,
       } => {
         for (c, b) in if_blocks {
           let e = self.execute_expression(c)?;
           match e {
-            Object::Boolean(true) => {
-              for s in b {
-                self.execute_statement(s)?;
-              }
-              return Ok(Object::Undefined);
-            }
+            Object::Boolean(true) => return self.execute_statements(b),
             Object::Boolean(false) => {}
             a => {
               return Err(RuntimeError::TypeMismatch {
@@ -116,68 +245,230 @@ impl Executor {
             }
           }
         }
-        for s in else_statements {
-          self.execute_statement(s)?;
+        self.execute_statements(else_statements)
+      }
+      StatementKind::SelectStatement {
+        expression,
+        case_blocks,
+        else_statements,
+      } => {
+        let value = self.execute_expression(expression)?;
+        for (conditions, statements) in case_blocks {
+          for c in conditions {
+            if self.matches_case(&value, c)? {
+              return self.execute_statements(statements);
+            }
+          }
+        }
+        self.execute_statements(else_statements)
+      }
+      StatementKind::ReturnStatement { expression } => {
+        if let Some(expression) = expression {
+          let evaluated = self.execute_expression(expression)?;
+          let ret_variable_name = String::from("Ret");
+          self.context.set_variable(&ret_variable_name, &evaluated)?;
         }
-        return Ok(Object::Undefined);
+        self.signal = Some(Signal::Return);
+        Ok(Object::Undefined)
       }
-      Statement::ReturnStatement {expression} => {
-        let evaluated = self.execute_expression(expression)?;
-        let ret_variable_name = String::from("Ret");
-        self.context.set_variable(&ret_variable_name, &evaluated)?;
-        self.to_return = true;
-        return Ok(Object::Undefined);
+      StatementKind::ExitStatement(block) => {
+        self.signal = Some(Signal::Exit(*block));
+        Ok(Object::Undefined)
+      }
+      StatementKind::ContinueStatement(block) => {
+        self.signal = Some(Signal::Continue(*block));
+        Ok(Object::Undefined)
+      }
+      StatementKind::Empty => Ok(Object::Undefined),
+    }
+  }
+
+  /// Consumes `Exit` and `Continue` of the loop `kind` at the end of an iteration.
+  /// Returns whether the loop stops; any other signal stops it and is passed on.
+  fn end_of_iteration(&mut self, kind: BlockKind) -> bool {
+    match self.signal {
+      Some(Signal::Exit(k)) if k == kind => {
+        self.signal = None;
+        true
       }
-      Statement::Empty => Ok(Object::Undefined),
+      Some(Signal::Continue(k)) if k == kind => {
+        self.signal = None;
+        false
+      }
+      Some(_) => true,
+      None => false,
+    }
+  }
+
+  /// Assigns the variable of a `For` loop, declaring it on first use so that loops can
+  /// reuse the same name.
+  fn bind_loop_variable(&mut self, name: &str, value: &Object) -> Result<(), RuntimeError> {
+    match self.context.set_variable(name, value) {
+      Err(RuntimeError::UndefinedVariable(_)) => self.context.declare_variable(name, value),
+      r => r,
+    }
+  }
+
+  /// Evaluates a bound of a `For` loop, which must be a number.
+  fn execute_number(&mut self, expression: &Expression) -> Result<Object, RuntimeError> {
+    match self.execute_expression(expression)? {
+      n
+      @ (Object::Integer(_) | Object::BigInteger(_) | Object::Double(_) | Object::Currency(_)) => {
+        Ok(n)
+      }
+      a => Err(
+        RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: a.type_of(),
+        }
+        .locate(&expression.span),
+      ),
+    }
+  }
+
+  /// Evaluates the bounds of `Dim a(1 To 10)` or `ReDim`.
+  fn execute_array_bounds(
+    &mut self,
+    dimensions: &[ArrayBound],
+  ) -> Result<Vec<(i32, i32)>, RuntimeError> {
+    let mut bounds: Vec<(i32, i32)> = vec![];
+    for d in dimensions {
+      let lower = match &d.lower {
+        Some(e) => self.execute_index(e)?,
+        None => 0,
+      };
+      bounds.push((lower, self.execute_index(&d.upper)?));
     }
+    Ok(bounds)
+  }
+
+  fn execute_indices(&mut self, indices: &[Expression]) -> Result<Vec<i32>, RuntimeError> {
+    let mut evaluated: Vec<i32> = vec![];
+    for i in indices {
+      evaluated.push(self.execute_index(i)?);
+    }
+    Ok(evaluated)
+  }
+
+  /// Evaluates an index or a bound of an array, which must be an Integer.
+  fn execute_index(&mut self, expression: &Expression) -> Result<i32, RuntimeError> {
+    let evaluated = self.execute_expression(expression)?;
+    match evaluated
+      .coerce(&RuntimeType::Integer)
+      .map_err(|e| e.locate(&expression.span))?
+    {
+      Object::Integer(i) => Ok(i),
+      a => Err(
+        RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: a.type_of(),
+        }
+        .locate(&expression.span),
+      ),
+    }
+  }
+
+  /// Returns whether a `Do` loop goes on. The condition must be Boolean as in `If`.
+  fn test_loop_condition(&mut self, condition: &LoopCondition) -> Result<bool, RuntimeError> {
+    let (expression, expected) = match condition {
+      LoopCondition::While(e) => (e, true),
+      LoopCondition::Until(e) => (e, false),
+    };
+    match self.execute_expression(expression)? {
+      Object::Boolean(b) => Ok(b == expected),
+      a => Err(
+        RuntimeError::TypeMismatch {
+          expected: RuntimeType::Boolean,
+          actual: a.type_of(),
+        }
+        .locate(&expression.span),
+      ),
+    }
+  }
+
+  fn matches_case(
+    &mut self,
+    value: &Object,
+    condition: &CaseCondition,
+  ) -> Result<bool, RuntimeError> {
+    match condition {
+      CaseCondition::Value(e) => self.compare(value, &BinaryOperator::EQ, e),
+      CaseCondition::Range(from, to) => Ok(
+        self.compare(value, &BinaryOperator::GE, from)?
+          && self.compare(value, &BinaryOperator::LE, to)?,
+      ),
+      CaseCondition::Comparison(operator, e) => self.compare(value, operator, e),
+    }
+  }
+
+  /// Compares `value` with `expression` as the binary operator `operator` does.
+  fn compare(
+    &mut self,
+    value: &Object,
+    operator: &BinaryOperator,
+    expression: &Expression,
+  ) -> Result<bool, RuntimeError> {
+    let r = self.execute_expression(expression)?;
+    match Self::execute_binary_operation(value.clone(), operator, r) {
+      Ok(Object::Boolean(b)) => Ok(b),
+      Ok(o) => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Boolean,
+        actual: o.type_of(),
+      }),
+      Err(e) => Err(e),
+    }
+    .map_err(|e| e.locate(&expression.span))
   }
 
   fn execute_method(
     &mut self,
-    identifier: &String,
-    arguments: &Vec<Expression>,
+    identifier: &str,
+    arguments: &Arguments,
   ) -> Result<Object, RuntimeError> {
     debug!("[Executor] >>>execute_method: {}", identifier);
-    let ret_variable_name = String::from("Ret");
-    let mut evaluated_arguments: Vec<Object> = vec![];
-    for a in arguments {
-      let e = self.execute_expression(a)?;
-      evaluated_arguments.push(e)
-    }
-
-    let r = match identifier.as_str() {
-      "Print" => {
-        for a in evaluated_arguments {
+    let builtin = match self.context.get_variable_ref(identifier) {
+      // A user procedure shadows the builtin of the same name.
+      Ok(Object::Function(_)) => None,
+      _ => Some(identifier.to_lowercase()),
+    };
+    let r = match builtin.as_deref() {
+      Some("print") => {
+        for a in self.execute_builtin_arguments(identifier, arguments)? {
           print!("{}", a);
         }
-        println!("");
+        println!();
         Object::Undefined
       }
-      _ => match self.context.get_variable(identifier)? {
+      Some("now" | "dateadd" | "datediff" | "year" | "month" | "day") => {
+        let evaluated_arguments = self.execute_builtin_arguments(identifier, arguments)?;
+        self.execute_date_function(identifier, &evaluated_arguments)?
+      }
+      Some("lbound" | "ubound") => {
+        let evaluated_arguments = self.execute_builtin_arguments(identifier, arguments)?;
+        self.execute_bound_function(identifier, &evaluated_arguments)?
+      }
+      Some("cbigint") => {
+        let evaluated_arguments = self.execute_builtin_arguments(identifier, arguments)?;
+        Self::execute_cbigint(identifier, &evaluated_arguments)?
+      }
+      Some("ccur") => {
+        let evaluated_arguments = self.execute_builtin_arguments(identifier, arguments)?;
+        Self::execute_ccur(identifier, &evaluated_arguments)?
+      }
+      Some("round") => {
+        let evaluated_arguments = self.execute_builtin_arguments(identifier, arguments)?;
+        Self::execute_round(identifier, &evaluated_arguments)?
+      }
+      _ => match self.context.get_variable_ref(identifier)? {
         Object::Function(f) => {
-          self.context.new_stack();
-          if evaluated_arguments.len() != f.arguments.len() {
-            return Err(RuntimeError::ArgumentMismatch(identifier.to_string()));
-          }
-          for i in 0..evaluated_arguments.len() {
-            let an = &f.arguments[i];
-            let av = &evaluated_arguments[i];
-            self.context.declare_variable(an, av)?;
-          }
-          self
-            .context
-            .declare_variable(&ret_variable_name, &Object::Undefined)?;
-          debug!("[Context]\n {}", self.context);
-          self.execute_statements(&f.statements)?;
-          self.to_return = false;
-          let r = self.context.get_variable(&ret_variable_name)?;
-          self.context.pop_stack();
-          r
+          let f = f.clone();
+          self.call_function(&f, arguments)?
         }
+        Object::Array(_) => self.execute_array_access(identifier, arguments)?,
         value => {
           return Err(RuntimeError::NonFunctionObjectIsInvoked(
             identifier.to_string(),
-            value,
+            value.clone(),
           ))
         }
       },
@@ -187,19 +478,419 @@ impl Executor {
     Ok(r)
   }
 
+  /// Reads an element of an array, e.g. `m(i, j)`.
+  fn execute_array_access(
+    &mut self,
+    identifier: &str,
+    arguments: &Arguments,
+  ) -> Result<Object, RuntimeError> {
+    if let Some((name, _)) = arguments.named.first() {
+      return Err(RuntimeError::ArgumentMismatch(
+        identifier.to_string(),
+        format!("index cannot be named {}", name),
+      ));
+    }
+    let index = self.execute_indices(&arguments.positional)?;
+    // An index may call a function which changes the variable, so it is looked up again.
+    self.read_element(identifier, &index)
+  }
+
+  fn read_element(&self, identifier: &str, index: &[i32]) -> Result<Object, RuntimeError> {
+    match self.context.get_variable_ref(identifier)? {
+      Object::Array(a) => a.get(index),
+      o => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Array,
+        actual: o.type_of(),
+      }),
+    }
+  }
+
+  fn write_element(
+    &mut self,
+    identifier: &str,
+    index: &[i32],
+    value: &Object,
+  ) -> Result<(), RuntimeError> {
+    match self.context.get_variable_mut(identifier)? {
+      Object::Array(a) => a.set(index, value),
+      o => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Array,
+        actual: o.type_of(),
+      }),
+    }
+  }
+
+  /// Evaluates the arguments of a builtin function, which takes positional arguments only.
+  fn execute_builtin_arguments(
+    &mut self,
+    identifier: &str,
+    arguments: &Arguments,
+  ) -> Result<Vec<Object>, RuntimeError> {
+    if let Some((name, _)) = arguments.named.first() {
+      return Err(RuntimeError::ArgumentMismatch(
+        identifier.to_string(),
+        format!("{} does not take named argument {}", identifier, name),
+      ));
+    }
+    let mut evaluated_arguments: Vec<Object> = vec![];
+    for a in arguments.positional.iter() {
+      evaluated_arguments.push(self.execute_expression(a)?);
+    }
+    Ok(evaluated_arguments)
+  }
+
+  /// Calls a Function or Sub in a new stack frame. `ByRef` parameters are written back to
+  /// the variables passed by the caller when the call returns.
+  fn call_function(
+    &mut self,
+    f: &FunctionDeclaration,
+    arguments: &Arguments,
+  ) -> Result<Object, RuntimeError> {
+    let bound = self.bind_arguments(f, arguments)?;
+    self.context.new_stack();
+    // The frame is popped even if the body fails, so that the caller's scope is intact.
+    let r = self.execute_function_body(f, &bound);
+    self.context.pop_stack();
+    let (r, written_back) = r?;
+    let references = bound.iter().filter_map(|(_, reference)| reference.as_ref());
+    for (reference, value) in references.zip(written_back) {
+      match reference {
+        Reference::Variable(name) => self.context.set_variable(name, &value)?,
+        Reference::Element(name, index) => self.write_element(name, index, &value)?,
+      }
+    }
+    Ok(r)
+  }
+
+  /// Executes the statements of `f` in its own frame, and returns its result and the values
+  /// of the parameters to be written back in the order of their references.
+  fn execute_function_body(
+    &mut self,
+    f: &FunctionDeclaration,
+    bound: &[(Object, Option<Reference>)],
+  ) -> Result<(Object, Vec<Object>), RuntimeError> {
+    let ret_variable_name = String::from("Ret");
+    for (p, (value, _)) in f.parameters.iter().zip(bound.iter()) {
+      // The `As` of a ParamArray is the type of its elements.
+      let type_name = if p.param_array {
+        Some(RuntimeType::Array)
+      } else {
+        p.type_name
+      };
+      self
+        .context
+        .declare_typed_variable(&p.name, &type_name, value)?;
+    }
+    self.context.declare_typed_variable(
+      &ret_variable_name,
+      &f.return_type,
+      &Self::default_value(&f.return_type),
+    )?;
+    debug!("[Context]\n {}", self.context);
+    self.execute_statements(&f.statements)?;
+    // `Return` and `Exit Function` end here. The parser rejects `Exit For` and the like
+    // outside of a loop, so no other signal reaches a function call.
+    self.signal = None;
+    let r = match f.kind {
+      ProcedureKind::Function => self.context.get_variable(&ret_variable_name)?,
+      ProcedureKind::Sub => Object::Undefined,
+    };
+    let mut written_back: Vec<Object> = vec![];
+    for (p, (_, reference)) in f.parameters.iter().zip(bound.iter()) {
+      if reference.is_some() {
+        written_back.push(self.context.get_variable(&p.name)?);
+      }
+    }
+    Ok((r, written_back))
+  }
+
+  /// Matches the arguments of a call to the parameters of `f` and evaluates them in the
+  /// caller's scope. Returns the value of each parameter with the variable or the array
+  /// element it refers to if it is `ByRef`.
+  fn bind_arguments(
+    &mut self,
+    f: &FunctionDeclaration,
+    arguments: &Arguments,
+  ) -> Result<Vec<(Object, Option<Reference>)>, RuntimeError> {
+    let mismatch = |reason: String| RuntimeError::ArgumentMismatch(f.identifier.clone(), reason);
+    let fixed = f.parameters.iter().take_while(|p| !p.param_array).count();
+    let mut given: Vec<Option<&Expression>> = vec![None; fixed];
+    let mut rest: Vec<&Expression> = vec![];
+    for (i, a) in arguments.positional.iter().enumerate() {
+      if i < fixed {
+        given[i] = Some(a);
+      } else if fixed < f.parameters.len() {
+        rest.push(a);
+      } else {
+        return Err(mismatch(format!(
+          "{} arguments are given for {} parameters",
+          arguments.positional.len(),
+          fixed
+        )));
+      }
+    }
+    for (name, a) in arguments.named.iter() {
+      let position = f.parameters[..fixed]
+        .iter()
+        .position(|p| p.name.to_lowercase() == name.to_lowercase());
+      match position {
+        None => return Err(mismatch(format!("there is no parameter named {}", name))),
+        Some(i) if given[i].is_some() => {
+          return Err(mismatch(format!(
+            "parameter {} is given twice",
+            f.parameters[i].name
+          )))
+        }
+        Some(i) => given[i] = Some(a),
+      }
+    }
+    let mut bound: Vec<(Object, Option<Reference>)> = vec![];
+    for (p, a) in f.parameters[..fixed].iter().zip(given) {
+      let value = match (a, &p.default) {
+        (Some(a), _) if p.passing == PassingMode::ByRef => self.execute_reference(a)?,
+        (Some(a), _) => (self.execute_expression(a)?, None),
+        (None, Some(d)) => (self.execute_expression(d)?, None),
+        (None, None) if p.optional => (Self::default_value(&p.type_name), None),
+        (None, None) => return Err(mismatch(format!("parameter {} is not given", p.name))),
+      };
+      bound.push(value);
+    }
+    if let Some(p) = f.parameters.get(fixed) {
+      let mut items = Array::new(vec![(0, rest.len() as i32 - 1)], p.type_name)?;
+      for (i, a) in rest.into_iter().enumerate() {
+        let value = self.execute_expression(a)?;
+        items.set(&[i as i32], &value)?;
+      }
+      bound.push((Object::Array(items), None));
+    }
+    Ok(bound)
+  }
+
+  /// Evaluates an argument for a `ByRef` parameter. A variable or an array element is
+  /// referred to, and the indices of the element are evaluated only once.
+  fn execute_reference(
+    &mut self,
+    argument: &Expression,
+  ) -> Result<(Object, Option<Reference>), RuntimeError> {
+    match &argument.kind {
+      ExpressionKind::Identifier(name) => Ok((
+        self.execute_expression(argument)?,
+        Some(Reference::Variable(name.clone())),
+      )),
+      ExpressionKind::FunctionInvocation {
+        identifier,
+        arguments,
+      } if arguments.named.is_empty()
+        && matches!(
+          self.context.get_variable_ref(identifier),
+          Ok(Object::Array(_))
+        ) =>
+      {
+        let index = self
+          .execute_indices(&arguments.positional)
+          .map_err(|e| e.locate(&argument.span))?;
+        let value = self
+          .read_element(identifier, &index)
+          .map_err(|e| e.locate(&argument.span))?;
+        Ok((value, Some(Reference::Element(identifier.clone(), index))))
+      }
+      _ => Ok((self.execute_expression(argument)?, None)),
+    }
+  }
+
+  /// `LBound(array)` and `UBound(array, dimension)`. Dimensions count from 1.
+  fn execute_bound_function(
+    &mut self,
+    identifier: &str,
+    arguments: &[Object],
+  ) -> Result<Object, RuntimeError> {
+    let (array, dimension) = match arguments {
+      [Object::Array(a)] => (a, 1),
+      [Object::Array(a), Object::Integer(d)] => (a, *d),
+      _ => {
+        return Err(RuntimeError::ArgumentMismatch(
+          identifier.to_string(),
+          format!(
+            "expected {}(array As Array, dimension As Integer)",
+            identifier
+          ),
+        ))
+      }
+    };
+    let bound = match identifier.to_lowercase().as_str() {
+      "lbound" => array.lower_bound(dimension)?,
+      _ => array.upper_bound(dimension)?,
+    };
+    Ok(Object::Integer(bound))
+  }
+
+  /// `CBigInt(value)` converts an Integer, a Double rounded half to even, or a String of
+  /// decimal digits.
+  fn execute_cbigint(identifier: &str, arguments: &[Object]) -> Result<Object, RuntimeError> {
+    let mismatch = |reason: String| RuntimeError::ArgumentMismatch(identifier.to_string(), reason);
+    let n = match arguments {
+      [Object::Integer(n)] => BigInt::from(*n),
+      [Object::BigInteger(n)] => n.clone(),
+      [Object::Double(d)] => match BigInt::from_f64(d.round_ties_even()) {
+        Some(n) => n,
+        None => return Err(mismatch(format!("{} is not a finite number", d))),
+      },
+      [Object::String(s)] => match s.trim().parse::<BigInt>() {
+        Ok(n) => n,
+        Err(_) => return Err(mismatch(format!("\"{}\" is not an integer", s))),
+      },
+      _ => {
+        return Err(mismatch(String::from(
+          "expected CBigInt(value As Integer, Double or String)",
+        )))
+      }
+    };
+    Ok(Object::BigInteger(n))
+  }
+
+  /// `CCur(value)` converts an Integer, a BigInteger, a Double rounded half to even at the
+  /// 4th decimal place, or a String of decimal digits such as `"19.99"`.
+  fn execute_ccur(identifier: &str, arguments: &[Object]) -> Result<Object, RuntimeError> {
+    let mismatch = |reason: String| RuntimeError::ArgumentMismatch(identifier.to_string(), reason);
+    let converted = match arguments {
+      [Object::Integer(n)] => Some(Currency::from(*n)),
+      [Object::BigInteger(n)] => n.to_i64().and_then(Currency::from_integer),
+      [Object::Double(d)] => Currency::from_double(*d),
+      [Object::Currency(c)] => Some(*c),
+      [Object::String(s)] => match Currency::parse(s.trim()) {
+        Some(c) => Some(c),
+        // A number which does not fit is out of range rather than not a number.
+        None if s.trim().parse::<f64>().is_ok() => None,
+        None => return Err(mismatch(format!("\"{}\" is not a number", s))),
+      },
+      _ => {
+        return Err(mismatch(String::from(
+          "expected CCur(value As Integer, Double or String)",
+        )))
+      }
+    };
+    match converted {
+      Some(c) => Ok(Object::Currency(c)),
+      None => Err(RuntimeError::ConversionOverflow {
+        value: arguments[0].clone(),
+        to: RuntimeType::Currency,
+      }),
+    }
+  }
+
+  /// `Round(number, digits)` rounds half to even as VB, e.g. `Round(2.5)` is `2` and
+  /// `Round(0.125@, 2)` is `0.12`. An Integer or a BigInteger is returned as it is.
+  fn execute_round(identifier: &str, arguments: &[Object]) -> Result<Object, RuntimeError> {
+    let mismatch = |reason: String| RuntimeError::ArgumentMismatch(identifier.to_string(), reason);
+    let (value, digits) = match arguments {
+      [value] => (value, 0),
+      [value, Object::Integer(d)] if (0..=15).contains(d) => (value, *d as u32),
+      _ => {
+        return Err(mismatch(String::from(
+          "expected Round(number, digits As Integer), where digits is 0 to 15",
+        )))
+      }
+    };
+    match value {
+      Object::Integer(_) | Object::BigInteger(_) => Ok(value.clone()),
+      Object::Double(d) => {
+        let scale = 10f64.powi(digits as i32);
+        // A Double too large to be scaled has no fraction to round.
+        match d * scale {
+          scaled if scaled.is_finite() => Ok(Object::Double(scaled.round_ties_even() / scale)),
+          _ => Ok(Object::Double(*d)),
+        }
+      }
+      Object::Currency(c) => match c.round(digits) {
+        Some(c) => Ok(Object::Currency(c)),
+        None => Err(mismatch(format!("{} is out of the range of Currency", c))),
+      },
+      v => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Double,
+        actual: v.type_of(),
+      }),
+    }
+  }
+
+  fn execute_date_function(
+    &mut self,
+    identifier: &str,
+    arguments: &[Object],
+  ) -> Result<Object, RuntimeError> {
+    let mismatch =
+      |reason: &str| RuntimeError::ArgumentMismatch(identifier.to_string(), reason.to_string());
+    let signature = match identifier.to_lowercase().as_str() {
+      "now" => "Now()",
+      "dateadd" => "DateAdd(interval As String, number As Integer, date As Date)",
+      "datediff" => "DateDiff(interval As String, date1 As Date, date2 As Date)",
+      "year" => "Year(date As Date)",
+      "month" => "Month(date As Date)",
+      _ => "Day(date As Date)",
+    };
+    match (identifier.to_lowercase().as_str(), arguments) {
+      ("now", []) => Ok(Object::Date(self.clock.now())),
+      ("dateadd", [Object::String(interval), Object::Integer(n), Object::Date(d)]) => {
+        match d.add_interval(interval, *n as i64) {
+          Some(Some(date)) => Ok(Object::Date(date)),
+          Some(None) => Err(RuntimeError::Overflow(
+            BinaryOperator::ADD,
+            Object::Date(*d),
+            Object::Integer(*n),
+          )),
+          None => Err(mismatch(&format!("unknown interval \"{}\"", interval))),
+        }
+      }
+      ("datediff", [Object::String(interval), Object::Date(l), Object::Date(r)]) => {
+        match l.diff(interval, r) {
+          Some(n) => Self::integer_result(n),
+          None => Err(mismatch(&format!("unknown interval \"{}\"", interval))),
+        }
+      }
+      ("year", [Object::Date(d)]) => Self::integer_result(d.year()),
+      ("month", [Object::Date(d)]) => Ok(Object::Integer(d.month() as i32)),
+      ("day", [Object::Date(d)]) => Ok(Object::Integer(d.day() as i32)),
+      _ => Err(mismatch(&format!("expected {}", signature))),
+    }
+  }
+
+  /// An Integer result of a builtin, which overflows if it is out of range.
+  fn integer_result(n: i64) -> Result<Object, RuntimeError> {
+    match i32::try_from(n) {
+      Ok(n) => Ok(Object::Integer(n)),
+      Err(_) => Err(RuntimeError::ConversionOverflow {
+        value: Object::BigInteger(BigInt::from(n)),
+        to: RuntimeType::Integer,
+      }),
+    }
+  }
+
   fn execute_const_assignment(
     &mut self,
-    identifier: &String,
-    expression: &Expression,
+    identifier: &str,
+    type_name: &Option<RuntimeType>,
+    expression: &Option<Expression>,
   ) -> Result<Object, RuntimeError> {
-    let evaluated = self.execute_expression(expression)?;
-    self.context.declare_variable(identifier, &evaluated)?;
+    let evaluated = match expression {
+      Some(e) => self.execute_expression(e)?,
+      None => Self::default_value(type_name),
+    };
+    self
+      .context
+      .declare_typed_variable(identifier, type_name, &evaluated)?;
     Ok(evaluated)
   }
 
+  /// Initial value of a variable of `type_name`. A Variant starts as Undefined.
+  fn default_value(type_name: &Option<RuntimeType>) -> Object {
+    match type_name {
+      Some(t) => t.default_value(),
+      None => Object::Undefined,
+    }
+  }
+
   fn execute_assignment(
     &mut self,
-    identifier: &String,
+    identifier: &str,
     expression: &Expression,
   ) -> Result<Object, RuntimeError> {
     let evaluated = self.execute_expression(expression)?;
@@ -207,85 +898,1294 @@ impl Executor {
     Ok(evaluated)
   }
 
+  /// Evaluates `expression`. An error is located at the innermost expression which raised it.
   fn execute_expression(&mut self, expression: &Expression) -> Result<Object, RuntimeError> {
+    self
+      .execute_expression_kind(&expression.kind)
+      .map_err(|e| e.locate(&expression.span))
+  }
+
+  fn execute_expression_kind(
+    &mut self,
+    expression: &ExpressionKind,
+  ) -> Result<Object, RuntimeError> {
     match expression {
-      Expression::Boolean(value) => Ok(Object::Boolean(*value)),
-      Expression::Identifier(name) => self.context.get_variable(name),
-      Expression::Integer(value) => Ok(Object::Integer(*value)),
-      Expression::String(value) => Ok(Object::String(value.clone())),
-      Expression::FunctionInvocation {
+      ExpressionKind::Boolean(value) => Ok(Object::Boolean(*value)),
+      ExpressionKind::Identifier(name) => match self.context.get_variable(name) {
+        Err(RuntimeError::UndefinedVariable(_)) if name.to_lowercase() == "now" => {
+          self.execute_method(name, &Arguments::default())
+        }
+        r => r,
+      },
+      ExpressionKind::Integer(value) => Ok(Object::Integer(*value)),
+      ExpressionKind::BigInteger(value) => Ok(Object::BigInteger(value.clone())),
+      ExpressionKind::Double(value) => Ok(Object::Double(*value)),
+      ExpressionKind::Currency(value) => Ok(Object::Currency(*value)),
+      ExpressionKind::Date(value) => Ok(Object::Date(*value)),
+      ExpressionKind::String(value) => Ok(Object::String(value.clone())),
+      ExpressionKind::InterpolatedString(parts) => {
+        let mut s = String::new();
+        for p in parts {
+          match p {
+            InterpolationPart::Text(text) => s.push_str(text),
+            // Converted as `&` does, e.g. a Boolean is `True`.
+            InterpolationPart::Expression(e) => s.push_str(
+              &self
+                .execute_expression(e)?
+                .to_text()
+                .map_err(|err| err.locate(&e.span))?,
+            ),
+          }
+        }
+        Ok(Object::String(s))
+      }
+      ExpressionKind::FunctionInvocation {
         identifier,
         arguments,
       } => self.execute_method(identifier, arguments),
-      Expression::Binary {
+      ExpressionKind::Binary {
         left,
         operator,
         right,
       } => {
-        let l = self.execute_expression(&left)?;
-        let r = self.execute_expression(&right)?;
-        match (l, r) {
-          (Object::Integer(l), Object::Integer(r)) => match operator {
-            BinaryOperator::ADD => Ok(Object::Integer(l + r)),
-            BinaryOperator::SUB => Ok(Object::Integer(l - r)),
-            BinaryOperator::MUL => Ok(Object::Integer(l * r)),
-            BinaryOperator::DIV => Ok(Object::Integer(l / r)),
-            BinaryOperator::MOD => Ok(Object::Integer(l % r)),
-            BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
-            BinaryOperator::NE => Ok(Object::Boolean(l != r)),
-            BinaryOperator::GT => Ok(Object::Boolean(l > r)),
-            BinaryOperator::LT => Ok(Object::Boolean(l < r)),
-            BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
-            BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
-            _ => Err(RuntimeError::TypeMismatch {
-              expected: RuntimeType::Integer,
-              actual: RuntimeType::Boolean,
-            }),
-          },
-          (Object::Boolean(l), Object::Boolean(r)) => match operator {
-            BinaryOperator::AND => Ok(Object::Boolean(l && r)),
-            BinaryOperator::XOR => Ok(Object::Boolean(l || r)),
-            BinaryOperator::OR => Ok(Object::Boolean(l || r)),
-            _ => Err(RuntimeError::TypeMismatch {
-              expected: RuntimeType::Boolean,
-              actual: RuntimeType::Integer,
-            }),
-          },
-          (l, r) => Err(RuntimeError::TypeMismatch {
-            expected: l.type_of(),
-            actual: r.type_of(),
-          }),
+        let l = self.execute_expression(left)?;
+        // `AndAlso` and `OrElse` skip the right operand if the left one decides the result,
+        // while `And` and `Or` always evaluate both as in VB.
+        match (operator, &l) {
+          (BinaryOperator::ANDALSO, Object::Boolean(false)) => return Ok(Object::Boolean(false)),
+          (BinaryOperator::ORELSE, Object::Boolean(true)) => return Ok(Object::Boolean(true)),
+          _ => {}
         }
+        let r = self.execute_expression(right)?;
+        Self::execute_binary_operation(l, operator, r)
       }
-      Expression::Unary {
+      ExpressionKind::Unary {
         operator,
         expression,
       } => {
-        let evaluated = self.execute_expression(&expression)?;
+        let evaluated = self.execute_expression(expression)?;
         match operator {
           UnaryOperator::NEGATIVE => match evaluated {
-            Object::Integer(n) => Ok(Object::Integer(-n)),
-            _ => Err(RuntimeError::TypeMismatch {
+            // -2147483648 has no positive counterpart, so negate as `0 - n` to check it.
+            Object::Integer(n) => Self::execute_integer_operation(0, &BinaryOperator::SUB, n),
+            Object::BigInteger(n) => Ok(integer(-n)),
+            Object::Currency(n) => {
+              Self::execute_currency_operation(Currency::from(0), &BinaryOperator::SUB, n)
+            }
+            Object::Double(n) => Ok(Object::Double(-n)),
+            value => Err(RuntimeError::TypeMismatch {
               expected: RuntimeType::Integer,
-              actual: RuntimeType::Integer,
+              actual: value.type_of(),
             }),
           },
           UnaryOperator::POSITIVE => match evaluated {
-            Object::Integer(n) => Ok(Object::Integer(n)),
-            _ => Err(RuntimeError::TypeMismatch {
+            n @ (Object::Integer(_)
+            | Object::BigInteger(_)
+            | Object::Double(_)
+            | Object::Currency(_)) => Ok(n),
+            value => Err(RuntimeError::TypeMismatch {
               expected: RuntimeType::Integer,
-              actual: RuntimeType::Integer,
+              actual: value.type_of(),
             }),
           },
           UnaryOperator::NOT => match evaluated {
             Object::Boolean(n) => Ok(Object::Boolean(!n)),
-            _ => Err(RuntimeError::TypeMismatch {
-              expected: RuntimeType::Integer,
-              actual: RuntimeType::Integer,
+            value => Err(RuntimeError::TypeMismatch {
+              expected: RuntimeType::Boolean,
+              actual: value.type_of(),
             }),
           },
         }
       }
     }
   }
+
+  /// Applies a binary operator to evaluated operands. `Select Case` compares with this too.
+  fn execute_binary_operation(
+    l: Object,
+    operator: &BinaryOperator,
+    r: Object,
+  ) -> Result<Object, RuntimeError> {
+    // A Date out of the years 1 to 9999 is an Overflow.
+    let date_result = |result: Option<Date>, l: Object, r: Object| match result {
+      Some(d) => Ok(Object::Date(d)),
+      None => Err(RuntimeError::Overflow(operator.clone(), l, r)),
+    };
+    match (l, r) {
+      (l, r) if *operator == BinaryOperator::CONCAT => {
+        Ok(Object::String(l.to_text()? + &r.to_text()?))
+      }
+      // Strings are compared by code point as VB's `Option Compare Binary`.
+      (Object::String(l), Object::String(r)) => match operator {
+        BinaryOperator::ADD => Ok(Object::String(l + &r)),
+        BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
+        BinaryOperator::NE => Ok(Object::Boolean(l != r)),
+        BinaryOperator::GT => Ok(Object::Boolean(l > r)),
+        BinaryOperator::LT => Ok(Object::Boolean(l < r)),
+        BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
+        BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
+        _ => Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: RuntimeType::String,
+        }),
+      },
+      (Object::Integer(l), Object::Integer(r)) => Self::execute_integer_operation(l, operator, r),
+      (Object::Double(l), Object::Double(r)) => Self::execute_double_operation(l, operator, r),
+      (Object::Integer(l), Object::Double(r)) => {
+        Self::execute_double_operation(l as f64, operator, r)
+      }
+      (Object::Double(l), Object::Integer(r)) => {
+        Self::execute_double_operation(l, operator, r as f64)
+      }
+      (Object::BigInteger(l), Object::BigInteger(r)) => {
+        Self::execute_big_integer_operation(l, operator, r)
+      }
+      (Object::BigInteger(l), Object::Integer(r)) => {
+        Self::execute_big_integer_operation(l, operator, BigInt::from(r))
+      }
+      (Object::Integer(l), Object::BigInteger(r)) => {
+        Self::execute_big_integer_operation(BigInt::from(l), operator, r)
+      }
+      (Object::Currency(l), Object::Currency(r)) => {
+        Self::execute_currency_operation(l, operator, r)
+      }
+      (Object::Currency(l), Object::Integer(r)) => {
+        Self::execute_currency_operation(l, operator, Currency::from(r))
+      }
+      (Object::Integer(l), Object::Currency(r)) => {
+        Self::execute_currency_operation(Currency::from(l), operator, r)
+      }
+      (Object::Currency(l), Object::Double(r)) => {
+        Self::execute_double_operation(l.to_double(), operator, r)
+      }
+      (Object::Double(l), Object::Currency(r)) => {
+        Self::execute_double_operation(l, operator, r.to_double())
+      }
+      (Object::Currency(l), Object::BigInteger(r)) => {
+        Self::execute_double_operation(l.to_double(), operator, to_double(&r))
+      }
+      (Object::BigInteger(l), Object::Currency(r)) => {
+        Self::execute_double_operation(to_double(&l), operator, r.to_double())
+      }
+      (Object::BigInteger(l), Object::Double(r)) => {
+        Self::execute_double_operation(to_double(&l), operator, r)
+      }
+      (Object::Double(l), Object::BigInteger(r)) => {
+        Self::execute_double_operation(l, operator, to_double(&r))
+      }
+      (Object::Date(l), Object::Date(r)) => match operator {
+        BinaryOperator::SUB => Ok(Object::Double(l.days_since(&r))),
+        BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
+        BinaryOperator::NE => Ok(Object::Boolean(l != r)),
+        BinaryOperator::GT => Ok(Object::Boolean(l > r)),
+        BinaryOperator::LT => Ok(Object::Boolean(l < r)),
+        BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
+        BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
+        _ => Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: RuntimeType::Date,
+        }),
+      },
+      (Object::Date(d), Object::Integer(n)) => match operator {
+        BinaryOperator::ADD => {
+          date_result(d.add_days(n as i64), Object::Date(d), Object::Integer(n))
+        }
+        BinaryOperator::SUB => {
+          date_result(d.add_days(-(n as i64)), Object::Date(d), Object::Integer(n))
+        }
+        _ => Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Date,
+          actual: RuntimeType::Integer,
+        }),
+      },
+      (Object::Integer(n), Object::Date(d)) => match operator {
+        BinaryOperator::ADD => {
+          date_result(d.add_days(n as i64), Object::Integer(n), Object::Date(d))
+        }
+        _ => Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: RuntimeType::Date,
+        }),
+      },
+      (Object::Boolean(l), Object::Boolean(r)) => match operator {
+        BinaryOperator::AND | BinaryOperator::ANDALSO => Ok(Object::Boolean(l && r)),
+        BinaryOperator::XOR => Ok(Object::Boolean(l ^ r)),
+        BinaryOperator::OR | BinaryOperator::ORELSE => Ok(Object::Boolean(l || r)),
+        _ => Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Boolean,
+          actual: RuntimeType::Integer,
+        }),
+      },
+      (l, r) => Err(RuntimeError::TypeMismatch {
+        expected: l.type_of(),
+        actual: r.type_of(),
+      }),
+    }
+  }
+
+  /// Arithmetic on Integers is checked, so a result out of range is an Overflow instead of
+  /// wrapping around.
+  fn execute_integer_operation(
+    l: i32,
+    operator: &BinaryOperator,
+    r: i32,
+  ) -> Result<Object, RuntimeError> {
+    // A result out of the range of Integer is computed again as BigInteger.
+    let checked = |result: Option<i32>| match result {
+      Some(n) => Ok(Object::Integer(n)),
+      None if r == 0 => Err(RuntimeError::DivisionByZero(
+        operator.clone(),
+        Object::Integer(l),
+        Object::Integer(r),
+      )),
+      None => Self::execute_big_integer_operation(BigInt::from(l), operator, BigInt::from(r)),
+    };
+    match operator {
+      BinaryOperator::ADD => checked(l.checked_add(r)),
+      BinaryOperator::SUB => checked(l.checked_sub(r)),
+      BinaryOperator::MUL => checked(l.checked_mul(r)),
+      BinaryOperator::INTDIV => checked(l.checked_div(r)),
+      BinaryOperator::MOD => checked(l.checked_rem(r)),
+      // `/` divides as Double even for Integers as in VB. `\` is the integer division.
+      BinaryOperator::DIV | BinaryOperator::EXPOTENTIAL => {
+        Self::execute_double_operation(l as f64, operator, r as f64)
+      }
+      BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
+      BinaryOperator::NE => Ok(Object::Boolean(l != r)),
+      BinaryOperator::GT => Ok(Object::Boolean(l > r)),
+      BinaryOperator::LT => Ok(Object::Boolean(l < r)),
+      BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
+      BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
+      _ => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Integer,
+        actual: RuntimeType::Boolean,
+      }),
+    }
+  }
+
+  /// Integer operands mixed with BigInteger are promoted to BigInteger before calling this.
+  /// A result which fits in an Integer is an Integer again.
+  fn execute_big_integer_operation(
+    l: BigInt,
+    operator: &BinaryOperator,
+    r: BigInt,
+  ) -> Result<Object, RuntimeError> {
+    match operator {
+      BinaryOperator::INTDIV | BinaryOperator::MOD if r.is_zero() => {
+        Err(RuntimeError::DivisionByZero(
+          operator.clone(),
+          Object::BigInteger(l),
+          Object::BigInteger(r),
+        ))
+      }
+      BinaryOperator::ADD => Ok(integer(l + r)),
+      BinaryOperator::SUB => Ok(integer(l - r)),
+      BinaryOperator::MUL => Ok(integer(l * r)),
+      // Both truncate toward zero, and the remainder has the sign of `l` as `\` and `Mod`.
+      BinaryOperator::INTDIV => Ok(integer(l / r)),
+      BinaryOperator::MOD => Ok(integer(l % r)),
+      BinaryOperator::DIV | BinaryOperator::EXPOTENTIAL => {
+        Self::execute_double_operation(to_double(&l), operator, to_double(&r))
+      }
+      BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
+      BinaryOperator::NE => Ok(Object::Boolean(l != r)),
+      BinaryOperator::GT => Ok(Object::Boolean(l > r)),
+      BinaryOperator::LT => Ok(Object::Boolean(l < r)),
+      BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
+      BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
+      _ => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::BigInteger,
+        actual: RuntimeType::Boolean,
+      }),
+    }
+  }
+
+  /// Integer operands mixed with Currency are promoted to Currency before calling this.
+  /// `+`, `-`, `*`, `/` and `Mod` are exact up to 4 decimal places, and `/` rounds the
+  /// rest half to even.
+  fn execute_currency_operation(
+    l: Currency,
+    operator: &BinaryOperator,
+    r: Currency,
+  ) -> Result<Object, RuntimeError> {
+    let checked = |result: Option<Currency>| match result {
+      Some(n) => Ok(Object::Currency(n)),
+      None => Err(RuntimeError::Overflow(
+        operator.clone(),
+        Object::Currency(l),
+        Object::Currency(r),
+      )),
+    };
+    match operator {
+      BinaryOperator::DIV | BinaryOperator::INTDIV | BinaryOperator::MOD if r.is_zero() => Err(
+        RuntimeError::DivisionByZero(operator.clone(), Object::Currency(l), Object::Currency(r)),
+      ),
+      BinaryOperator::ADD => checked(l.checked_add(&r)),
+      BinaryOperator::SUB => checked(l.checked_sub(&r)),
+      BinaryOperator::MUL => checked(l.checked_mul(&r)),
+      BinaryOperator::DIV => checked(l.checked_div(&r)),
+      BinaryOperator::MOD => checked(l.checked_rem(&r)),
+      BinaryOperator::INTDIV | BinaryOperator::EXPOTENTIAL => {
+        Self::execute_double_operation(l.to_double(), operator, r.to_double())
+      }
+      BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
+      BinaryOperator::NE => Ok(Object::Boolean(l != r)),
+      BinaryOperator::GT => Ok(Object::Boolean(l > r)),
+      BinaryOperator::LT => Ok(Object::Boolean(l < r)),
+      BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
+      BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
+      _ => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Currency,
+        actual: RuntimeType::Boolean,
+      }),
+    }
+  }
+
+  /// Integer operands mixed with Double are promoted to Double before calling this.
+  /// A result which is too large for a Double is an Overflow as in VB, not infinity, and so
+  /// is a result which is not a number, e.g. `(-8) ^ (1 / 3)`.
+  fn execute_double_operation(
+    l: f64,
+    operator: &BinaryOperator,
+    r: f64,
+  ) -> Result<Object, RuntimeError> {
+    let division_by_zero =
+      || RuntimeError::DivisionByZero(operator.clone(), Object::Double(l), Object::Double(r));
+    let finite = |n: f64| {
+      if !n.is_finite() {
+        Err(RuntimeError::Overflow(
+          operator.clone(),
+          Object::Double(l),
+          Object::Double(r),
+        ))
+      } else {
+        Ok(Object::Double(n))
+      }
+    };
+    match operator {
+      BinaryOperator::ADD => finite(l + r),
+      BinaryOperator::SUB => finite(l - r),
+      BinaryOperator::MUL => finite(l * r),
+      BinaryOperator::DIV if r == 0.0 => Err(division_by_zero()),
+      BinaryOperator::DIV => finite(l / r),
+      // Operands are rounded half to even as VB's `CInt`.
+      BinaryOperator::INTDIV | BinaryOperator::MOD => {
+        let (l, r) = (l.round_ties_even(), r.round_ties_even());
+        let range = i32::MIN as f64..=i32::MAX as f64;
+        if !range.contains(&l) || !range.contains(&r) {
+          return Err(RuntimeError::Overflow(
+            operator.clone(),
+            Object::Double(l),
+            Object::Double(r),
+          ));
+        }
+        Self::execute_integer_operation(l as i32, operator, r as i32)
+      }
+      BinaryOperator::EXPOTENTIAL if l == 0.0 && r < 0.0 => Err(division_by_zero()),
+      BinaryOperator::EXPOTENTIAL => finite(l.powf(r)),
+      BinaryOperator::EQ => Ok(Object::Boolean(l == r)),
+      BinaryOperator::NE => Ok(Object::Boolean(l != r)),
+      BinaryOperator::GT => Ok(Object::Boolean(l > r)),
+      BinaryOperator::LT => Ok(Object::Boolean(l < r)),
+      BinaryOperator::LE => Ok(Object::Boolean(l <= r)),
+      BinaryOperator::GE => Ok(Object::Boolean(l >= r)),
+      _ => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::Double,
+        actual: RuntimeType::Boolean,
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::date::FixedClock;
+  use super::super::input_stream::InputStream;
+  use super::super::lexer::Lexer;
+  use super::super::parser::Parser;
+  use super::super::type_checker::TypeChecker;
+  use super::*;
+  use num_bigint::BigInt;
+
+  /// Parses, checks and runs `source`, and returns the executor with the result.
+  fn execute(source: &str) -> (Executor, Result<Object, RuntimeError>) {
+    let lexer = Lexer::new(InputStream::new(source, String::from("test.bs")));
+    let (program, errors) = Parser::new(lexer).parse_program();
+    assert!(errors.is_empty(), "{:?}", errors);
+    let errors = TypeChecker::new().check(&program);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let mut e = Executor::new();
+    let r = e.execute(&program);
+    (e, r)
+  }
+
+  /// Runs `source`, and returns the executor to read its variables.
+  fn run(source: &str) -> Result<Executor, RuntimeError> {
+    let (e, r) = execute(source);
+    r.map(|_| e)
+  }
+
+  fn value_of(source: &str, name: &str) -> Object {
+    match run(source) {
+      Ok(mut e) => e.context.get_variable(name).unwrap(),
+      Err(e) => panic!("{}", e),
+    }
+  }
+
+  /// The error raised by `source` without its location.
+  fn error_of(source: &str) -> RuntimeError {
+    match run(source) {
+      Ok(_) => panic!("no error is raised"),
+      Err(RuntimeError::Located { error, .. }) => *error,
+      Err(e) => e,
+    }
+  }
+
+  fn big(text: &str) -> Object {
+    Object::BigInteger(text.parse::<BigInt>().unwrap())
+  }
+
+  #[test]
+  fn integer_overflow_is_promoted_to_big_integer() {
+    let source = "Dim a = 2147483647\nDim b = a + 1\nDim c = a * a * a\nDim d = -2147483647 - 2";
+    assert_eq!(value_of(source, "b"), big("2147483648"));
+    assert_eq!(value_of(source, "c"), big("9903520300447984150353281023"));
+    assert_eq!(value_of(source, "d"), big("-2147483649"));
+  }
+
+  #[test]
+  fn big_integer_result_which_fits_is_an_integer() {
+    let source = "Dim x As Integer = 2147483647\nDim y As Integer = (x + 1) - 1";
+    assert_eq!(value_of(source, "y"), Object::Integer(2147483647));
+    let source = "Dim z = CBigInt(\"10000000000\") \\ 10000";
+    assert_eq!(value_of(source, "z"), Object::Integer(1000000));
+  }
+
+  #[test]
+  fn big_integer_index_is_narrowed() {
+    let source = "Dim a(2)\na(1) = 5\nDim x = 2147483647\nDim v = a((x + 1) - 2147483647)";
+    assert_eq!(value_of(source, "v"), Object::Integer(5));
+  }
+
+  #[test]
+  fn storing_big_integer_into_integer_overflows() {
+    assert_eq!(
+      error_of("Dim x As Integer = 2147483647\nx = x + 1"),
+      RuntimeError::ConversionOverflow {
+        value: big("2147483648"),
+        to: RuntimeType::Integer,
+      }
+    );
+  }
+
+  #[test]
+  fn big_integer_arithmetic_and_comparison() {
+    let source = "Dim n = CBigInt(\"123456789012345678901234567890\")
+Dim q = n \\ 7
+Dim m = CBigInt(\"-7\") Mod 3
+Dim h = n / 2
+Dim g = n > 2147483647
+Dim r = CBigInt(2.5) + CBigInt(3.5)";
+    assert_eq!(value_of(source, "q"), big("17636684144620811271604938270"));
+    assert_eq!(value_of(source, "m"), Object::Integer(-1));
+    assert_eq!(value_of(source, "h"), Object::Double(6.172839450617284e28));
+    assert_eq!(value_of(source, "g"), Object::Boolean(true));
+    assert_eq!(value_of(source, "r"), Object::Integer(6));
+    assert_eq!(
+      error_of("Dim z = CBigInt(5) \\ 0"),
+      RuntimeError::DivisionByZero(BinaryOperator::INTDIV, big("5"), big("0"))
+    );
+  }
+
+  #[test]
+  fn fibonacci_beyond_integer_is_exact() {
+    let source = "Dim f0 = 0
+Dim f1 = 1
+Dim t = 0
+For i = 1 To 100
+  t = f0 + f1
+  f0 = f1
+  f1 = t
+Next";
+    let f100 = value_of(source, "f0");
+    assert_eq!(f100, big("354224848179261915075"));
+    assert_eq!(f100.to_string(), "354224848179261915075");
+  }
+
+  #[test]
+  fn for_loop_over_currency() {
+    let source = "Dim total@ = 0\nFor c = 1@ To 2@ Step 0.5@\n  total = total + c\nNext";
+    assert_eq!(value_of(source, "total").to_string(), "4.5");
+  }
+
+  #[test]
+  fn by_ref_writes_back_to_variable_and_array_element() {
+    let source = "Dim a(2) As Integer
+a(1) = 10
+Dim n = 1
+Dim i = 1
+Inc(n)
+Inc(a(i))
+Inc(a(Next1(i)))
+
+Sub Inc(ByRef x)
+  x = x + 1
+End Sub
+
+Function Next1(ByRef k)
+  k = k + 1
+  Ret = k
+End Function";
+    let mut e = run(source).unwrap();
+    assert_eq!(e.context.get_variable("n").unwrap(), Object::Integer(2));
+    assert_eq!(e.read_element("a", &[1]).unwrap(), Object::Integer(11));
+    // The index is evaluated once, so `Next1` increments `i` only once.
+    assert_eq!(e.read_element("a", &[2]).unwrap(), Object::Integer(1));
+    assert_eq!(e.context.get_variable("i").unwrap(), Object::Integer(2));
+  }
+
+  #[test]
+  fn by_val_does_not_write_back() {
+    let source = "Dim n = 1\nInc(n)\nSub Inc(ByVal x)\n  x = x + 1\nEnd Sub";
+    assert_eq!(value_of(source, "n"), Object::Integer(1));
+  }
+
+  #[test]
+  fn failed_call_pops_its_frame() {
+    let source = "Dim x = 1
+Dim v = \"a\"
+S(5, v)
+
+Sub S(x, y As Integer)
+End Sub";
+    let (mut e, r) = execute(source);
+    assert!(r.is_err());
+    assert_eq!(e.context.get_variable("x").unwrap(), Object::Integer(1));
+  }
+
+  #[test]
+  fn optional_named_and_param_array_arguments() {
+    let source = "Dim a = F(1)
+Dim b = F(1, y:=2)
+Dim c = Sum(1, 2, 3)
+
+Function F(x, Optional y = 5)
+  Ret = x * 10 + y
+End Function
+
+Function Sum(ParamArray xs())
+  Ret = 0
+  For Each x In xs
+    Ret = Ret + x
+  Next
+End Function";
+    assert_eq!(value_of(source, "a"), Object::Integer(15));
+    assert_eq!(value_of(source, "b"), Object::Integer(12));
+    assert_eq!(value_of(source, "c"), Object::Integer(6));
+    assert_eq!(
+      error_of("F(1, z:=2)\nSub F(x)\nEnd Sub"),
+      RuntimeError::ArgumentMismatch(
+        String::from("F"),
+        String::from("there is no parameter named z")
+      )
+    );
+  }
+
+  #[test]
+  fn logical_operators_truth_table() {
+    let cases = [(false, false), (false, true), (true, false), (true, true)];
+    for (l, r) in cases {
+      let (a, b) = (Object::Boolean(l), Object::Boolean(r));
+      let apply = |operator: BinaryOperator| {
+        Executor::execute_binary_operation(a.clone(), &operator, b.clone()).unwrap()
+      };
+      assert_eq!(apply(BinaryOperator::AND), Object::Boolean(l && r));
+      assert_eq!(apply(BinaryOperator::ANDALSO), Object::Boolean(l && r));
+      assert_eq!(apply(BinaryOperator::OR), Object::Boolean(l || r));
+      assert_eq!(apply(BinaryOperator::ORELSE), Object::Boolean(l || r));
+      assert_eq!(apply(BinaryOperator::XOR), Object::Boolean(l != r));
+    }
+  }
+
+  #[test]
+  fn and_also_and_or_else_short_circuit() {
+    let source = "Dim a(2)
+a(2) = 5
+Dim i = 3
+Dim guarded = i <= UBound(a) AndAlso a(i) > 0
+Dim guarded2 = i > UBound(a) OrElse a(i) > 0
+Dim mixed = False OrElse True AndAlso False";
+    assert_eq!(value_of(source, "guarded"), Object::Boolean(false));
+    assert_eq!(value_of(source, "guarded2"), Object::Boolean(true));
+    assert_eq!(value_of(source, "mixed"), Object::Boolean(false));
+    assert_eq!(
+      error_of("Dim a(2)\nDim i = 3\nDim eager = i <= UBound(a) And a(i) > 0"),
+      RuntimeError::IndexOutOfRange {
+        index: vec![3],
+        bounds: vec![(0, 2)],
+      }
+    );
+  }
+
+  #[test]
+  fn interpolated_string_converts_as_concatenation() {
+    let source = "Dim b = True
+Dim n = 2
+Dim s = $\"b={b}, n*2={n * 2}, {{x}}\"
+Dim t = \"b=\" & b";
+    assert_eq!(
+      value_of(source, "s"),
+      Object::String(String::from("b=True, n*2=4, {x}"))
+    );
+    assert_eq!(
+      value_of(source, "t"),
+      Object::String(String::from("b=True"))
+    );
+  }
+
+  #[test]
+  fn huge_dim_is_a_runtime_error() {
+    assert_eq!(
+      error_of("Dim a(2147483647)"),
+      RuntimeError::ArrayTooLarge(vec![(0, 2147483647)])
+    );
+  }
+
+  #[test]
+  fn arrays_declare_index_and_redim() {
+    let source = "Dim m(1 To 2, 3) As Integer
+m(2, 3) = 7
+Dim a()
+ReDim a(2)
+a(2) = \"x\"
+ReDim Preserve a(4)
+Dim v = m(2, 3) + UBound(m, 2) + LBound(m)
+Dim w = a(2)
+Dim u = UBound(a)";
+    assert_eq!(value_of(source, "v"), Object::Integer(11));
+    assert_eq!(value_of(source, "w"), Object::String(String::from("x")));
+    assert_eq!(value_of(source, "u"), Object::Integer(4));
+  }
+
+  #[test]
+  fn double_results_which_are_not_numbers_overflow() {
+    assert!(matches!(
+      error_of("Dim x = (-8) ^ (1 / 3)"),
+      RuntimeError::Overflow(BinaryOperator::EXPOTENTIAL, _, _)
+    ));
+    assert!(matches!(
+      error_of("Dim x = 1e300 * 1e300"),
+      RuntimeError::Overflow(BinaryOperator::MUL, _, _)
+    ));
+  }
+
+  #[test]
+  fn datediff_out_of_integer_range_overflows() {
+    let source = "Dim n = DateDiff(\"d\", #2000-01-01#, #2000-03-01#)";
+    assert_eq!(value_of(source, "n"), Object::Integer(60));
+    assert_eq!(
+      error_of("Dim n = DateDiff(\"s\", #1900-01-01#, #2100-01-01#)"),
+      RuntimeError::ConversionOverflow {
+        value: Object::BigInteger(BigInt::from(6311433600i64)),
+        to: RuntimeType::Integer,
+      }
+    );
+  }
+
+  #[test]
+  fn dates_out_of_the_year_range_overflow() {
+    let last = Object::Date(Date::parse("9999-12-31").unwrap());
+    let first = Object::Date(Date::parse("0001-01-01").unwrap());
+    assert_eq!(value_of("Dim d = #9999-12-30# + 1", "d"), last);
+    assert_eq!(
+      error_of("Dim d = #9999-12-31# + 1"),
+      RuntimeError::Overflow(BinaryOperator::ADD, last, Object::Integer(1))
+    );
+    assert_eq!(
+      error_of("Dim d = #0001-01-01# - 1"),
+      RuntimeError::Overflow(BinaryOperator::SUB, first.clone(), Object::Integer(1))
+    );
+    assert_eq!(
+      error_of("Dim d = -1 + #0001-01-01#"),
+      RuntimeError::Overflow(BinaryOperator::ADD, Object::Integer(-1), first)
+    );
+    let d = Object::Date(Date::parse("2026-10-18").unwrap());
+    assert_eq!(
+      error_of("Dim d = DateAdd(\"yyyy\", -10000, #2026-10-18#)"),
+      RuntimeError::Overflow(BinaryOperator::ADD, d.clone(), Object::Integer(-10000))
+    );
+    assert_eq!(
+      error_of("Dim d = DateAdd(\"d\", 3000000, #2026-10-18#)"),
+      RuntimeError::Overflow(BinaryOperator::ADD, d, Object::Integer(3000000))
+    );
+  }
+
+  #[test]
+  fn round_keeps_doubles_too_large_to_scale() {
+    let source = "Dim r = Round(1e300, 15)";
+    assert_eq!(value_of(source, "r"), Object::Double(1e300));
+  }
+
+  #[test]
+  fn minimum_integer_literal_fits_in_an_integer() {
+    let source = "Dim x As Integer = -2147483648";
+    assert_eq!(value_of(source, "x"), Object::Integer(i32::MIN));
+    assert!(matches!(
+      error_of("Dim x As Integer = -2147483649"),
+      RuntimeError::ConversionOverflow { .. }
+    ));
+  }
+
+  #[test]
+  fn now_reads_the_injected_clock() {
+    let source = "Dim d = DateAdd(\"m\", 1, Now())
+Dim n = DateDiff(\"d\", #10/18/2026#, d)";
+    let lexer = Lexer::new(InputStream::new(source, String::from("test.bs")));
+    let (program, _) = Parser::new(lexer).parse_program();
+    let now = Date::parse("2026-10-18 09:00:00").unwrap();
+    let mut e = Executor::with_clock(Box::new(FixedClock(now)));
+    e.execute(&program).unwrap();
+    assert_eq!(
+      e.context.get_variable("d"),
+      Ok(Object::Date(Date::parse("2026-11-18 09:00:00").unwrap()))
+    );
+    assert_eq!(e.context.get_variable("n"), Ok(Object::Integer(31)));
+  }
+
+  #[test]
+  fn strings_compare_by_code_point() {
+    let source = "Dim a = \"abc\" = \"abc\"
+Dim b = \"B\" < \"a\"
+Dim c = \"ab\" >= \"abc\"
+Dim d = \"x\" <> \"X\"";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    let expected = [true, true, false, true].map(Object::Boolean);
+    assert_eq!(values, expected);
+  }
+
+  #[test]
+  fn select_case_matches_strings() {
+    let source = "Dim s = \"\"
+Dim a(3) As String
+a(0) = \"a\"
+a(1) = \"k\"
+a(2) = \"z\"
+a(3) = \"q\"
+For Each c In a
+  Select Case c
+    Case \"a\"
+      s = s & \"1\"
+    Case \"b\" To \"m\"
+      s = s & \"2\"
+    Case Is > \"r\"
+      s = s & \"3\"
+    Case Else
+      s = s & \"4\"
+  End Select
+Next";
+    assert_eq!(value_of(source, "s"), Object::String(String::from("1234")));
+  }
+
+  #[test]
+  fn single_line_if_runs_the_else_part() {
+    let source = "Dim s = \"\"
+If False Then Else s = s & \"a\"
+If True Then Else s = s & \"b\"
+If 1 > 2 Then s = s & \"c\": s = s & \"d\" Else s = s & \"e\": s = s & \"f\"";
+    assert_eq!(value_of(source, "s"), Object::String(String::from("aef")));
+  }
+
+  #[test]
+  fn integers_mixed_with_doubles_are_promoted() {
+    let source = "Dim a = 1 + 0.5\nDim b = 7 / 2\nDim c = 2 * 1.25 = 2.5\nDim d = 0.1 + 0.2";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      vec![
+        Object::Double(1.5),
+        Object::Double(3.5),
+        Object::Boolean(true),
+        Object::Double(0.1 + 0.2)
+      ]
+    );
+  }
+
+  #[test]
+  fn interpolated_string_escapes_braces_and_quotes() {
+    let source = "Dim n = 3\nDim s = $\"{{n}} = {n * 2} \"\"q\"\" {\"a\"\"b\" & n}\"";
+    assert_eq!(
+      value_of(source, "s"),
+      Object::String(String::from("{n} = 6 \"q\" a\"b3"))
+    );
+  }
+
+  #[test]
+  fn colons_separate_statements_and_continued_lines_are_joined() {
+    let source = "Function Add(a, b, _
+    c)
+  Return a + _
+    b + c
+End Function
+Dim x = 1: x = Add(x, _
+  2, _
+  3)
+If x = 6 Then: x = x * 10: End If";
+    assert_eq!(value_of(source, "x"), Object::Integer(60));
+  }
+
+  #[test]
+  fn runtime_errors_report_the_innermost_span() {
+    let located = |source: &str| execute(source).1.unwrap_err().to_string();
+    assert_eq!(
+      located("Dim a = 1\nDim b = 2 + a \\ (a - 1)"),
+      "DivisionByZero, 1 \\ 0 divides by zero.\n\
+       line: 2, column: 13 - line: 2, column: 24, at test.bs"
+    );
+    assert_eq!(
+      located("Function F(x)\n  Return x \\ 0\nEnd Function\nDim b = F(1)"),
+      "DivisionByZero, 1 \\ 0 divides by zero.\n\
+       line: 2, column: 10 - line: 2, column: 15, at test.bs"
+    );
+    assert_eq!(
+      located("Dim a(2)\na(5) = 1"),
+      "IndexOutOfRange, (5) is out of (0 To 2).\n\
+       line: 2, column: 1 - line: 2, column: 9, at test.bs"
+    );
+  }
+
+  #[test]
+  fn else_if_chains_run_the_first_true_block() {
+    let source = "Function Grade(n)
+  If n >= 90 Then
+    Return \"A\"
+  ElseIf n >= 80 Then
+    Return \"B\"
+  Else If n >= 70 Then
+    Return \"C\"
+  Else
+    Return \"F\"
+  End If
+End Function
+Dim s = Grade(95) & Grade(85) & Grade(90) & Grade(75) & Grade(10)";
+    assert_eq!(value_of(source, "s"), Object::String(String::from("ABACF")));
+  }
+
+  #[test]
+  fn select_case_runs_the_first_matching_case() {
+    let source = "Function Kind(n)
+  Select Case n
+    Case 0
+      Return \"zero\"
+    Case 1, 3, 5 To 7
+      Return \"odd\"
+    Case Is < 0
+      Return \"negative\"
+    Case 2 To 9
+      Return \"small\"
+    Case Else
+      Return \"large\"
+  End Select
+End Function
+Dim s = Kind(0) & \" \" & Kind(6) & \" \" & Kind(-4) & \" \" & Kind(4) & \" \" & Kind(10)";
+    assert_eq!(
+      value_of(source, "s"),
+      Object::String(String::from("zero odd negative small large"))
+    );
+    let source = "Dim x = 1\nSelect Case 5\n  Case 1 To 4\n    x = 2\nEnd Select";
+    assert_eq!(value_of(source, "x"), Object::Integer(1));
+  }
+
+  #[test]
+  fn do_and_while_loops() {
+    let source = "Dim a = 0
+Do While a < 3
+  a = a + 1
+Loop
+Dim b = 0
+Do Until b >= 3
+  b = b + 2
+Loop
+Dim c = 10
+Do
+  c = c + 1
+Loop While c < 5
+Dim d = 0
+Do
+  d = d + 1
+Loop Until d = 4
+Dim e = 1
+While e < 100
+  e = e * 3
+Wend";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d", "e"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(values, [3, 4, 11, 4, 243].map(Object::Integer).to_vec());
+  }
+
+  #[test]
+  fn for_loops_with_steps() {
+    let source = "Dim s = \"\"
+For i = 10 To 1 Step -3
+  s = s & i & \",\"
+Next
+For x = 0 To 1 Step 0.25
+  s = s & x & \",\"
+Next
+For j = 5 To 1
+  s = s & \"never\"
+Next
+Dim last = 0
+For k = 1 To 3
+  last = k
+Next k";
+    assert_eq!(
+      value_of(source, "s"),
+      Object::String(String::from("10,7,4,1,0,0.25,0.5,0.75,1,"))
+    );
+    assert_eq!(value_of(source, "last"), Object::Integer(3));
+  }
+
+  #[test]
+  fn for_each_over_strings_and_arrays() {
+    let source = "Dim s = \"\"
+For Each c In \"abc\"
+  s = c & s
+Next
+Dim m(1, 1) As Integer
+m(0, 1) = 1
+m(1, 0) = 2
+For Each n In m
+  s = s & n
+Next";
+    assert_eq!(
+      value_of(source, "s"),
+      Object::String(String::from("cba0120"))
+    );
+  }
+
+  #[test]
+  fn exit_and_continue() {
+    let source = "Function FirstOver(limit)
+  For i = 1 To 100
+    If i * i > limit Then
+      Return i
+      Exit Function
+    End If
+  Next
+End Function
+Sub Skip()
+  Exit Sub
+  Print(\"never\")
+End Sub
+Dim s = \"\"
+For i = 1 To 3
+  For j = 1 To 3
+    If j = 2 Then Continue For
+    If j = 3 Then Exit For
+    s = s & i & j & \",\"
+  Next
+Next
+Dim n = 0
+Do
+  n = n + 1
+  If n Mod 2 = 0 Then Continue Do
+  If n > 6 Then Exit Do
+Loop
+Skip()
+Dim f = FirstOver(50)";
+    assert_eq!(
+      value_of(source, "s"),
+      Object::String(String::from("11,21,31,"))
+    );
+    assert_eq!(value_of(source, "n"), Object::Integer(7));
+    assert_eq!(value_of(source, "f"), Object::Integer(8));
+  }
+
+  #[test]
+  fn declarations_without_initializer_take_the_default_value() {
+    let source = "Dim i As Integer
+Dim d As Double
+Dim s As String
+Dim b As Boolean
+Dim t As Date
+Dim c As Currency
+Dim v";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["i", "d", "s", "b", "t", "c", "v"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      vec![
+        Object::Integer(0),
+        Object::Double(0.0),
+        Object::String(String::new()),
+        Object::Boolean(false),
+        Object::Date(Date::parse("1899-12-30").unwrap()),
+        Object::Currency(Currency::from(0)),
+        Object::Undefined
+      ]
+    );
+  }
+
+  #[test]
+  fn evaluation_follows_precedence_and_associativity() {
+    let source = "Dim a = 2 ^ 3 ^ 2\nDim b = -2 ^ 2\nDim c = 10 - 4 - 3\nDim d = 1 + 2 * 3 Mod 4";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      vec![
+        Object::Double(64.0),
+        Object::Double(-4.0),
+        Object::Integer(3),
+        Object::Integer(3)
+      ]
+    );
+  }
+
+  #[test]
+  fn arithmetic_operators_follow_vb() {
+    let source = "Dim a = 7 \\ 2
+Dim b = -7 \\ 2
+Dim c = 7.5 \\ 2
+Dim d = 2.5 \\ 1
+Dim e = -7 Mod 3
+Dim f = 7 % 3
+Dim g = 5.5 Mod 2
+Dim h = 2 ^ -1
+Dim i = 7.5 Mod 2
+Dim j = 8.5 Mod 3";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      vec![
+        Object::Integer(3),
+        Object::Integer(-3),
+        Object::Integer(4),
+        Object::Integer(2),
+        Object::Integer(-1),
+        Object::Integer(1),
+        Object::Integer(0),
+        Object::Double(0.5),
+        Object::Integer(0),
+        Object::Integer(2)
+      ]
+    );
+  }
+
+  #[test]
+  fn concatenation_converts_operands_to_strings() {
+    let source = "Dim a = \"n=\" & 1 + 2
+Dim b = \"a\" + \"b\"
+Dim c = True & 1.5 & #2026-10-18#";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      ["n=3", "ab", "True1.52026-10-18"]
+        .map(|s| Object::String(String::from(s)))
+        .to_vec()
+    );
+  }
+
+  #[test]
+  fn division_by_zero_is_an_error() {
+    for source in [
+      "Dim x = 1 \\ 0",
+      "Dim x = 1 Mod 0",
+      "Dim x = 1 / 0",
+      "Dim x = 1.5 \\ 0.2",
+    ] {
+      assert!(
+        matches!(error_of(source), RuntimeError::DivisionByZero(..)),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn currency_out_of_range_overflows() {
+    assert!(matches!(
+      error_of("Dim c = 922337203685477@ * 10"),
+      RuntimeError::Overflow(BinaryOperator::MUL, _, _)
+    ));
+    for source in [
+      "Dim c = CCur(1e300)",
+      "Dim c = CCur(\"99999999999999999999\")",
+    ] {
+      assert!(
+        matches!(
+          error_of(source),
+          RuntimeError::ConversionOverflow {
+            to: RuntimeType::Currency,
+            ..
+          }
+        ),
+        "{}",
+        source
+      );
+    }
+    assert!(matches!(
+      error_of("Dim c = CCur(\"abc\")"),
+      RuntimeError::ArgumentMismatch(..)
+    ));
+  }
+
+  #[test]
+  fn round_is_bankers_rounding() {
+    let source = "Dim a = Round(2.5)
+Dim b = Round(3.5)
+Dim c = Round(-2.5)
+Dim d = Round(0.125@, 2)
+Dim e = Round(1.005, 2)
+Dim f = Round(7)";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d", "e", "f"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      vec![
+        Object::Double(2.0),
+        Object::Double(4.0),
+        Object::Double(-2.0),
+        Object::Currency(Currency::parse("0.12").unwrap()),
+        // 1.005 is slightly less than 1.005 as a Double.
+        Object::Double(1.0),
+        Object::Integer(7)
+      ]
+    );
+  }
+
+  #[test]
+  fn currency_arithmetic_is_exact() {
+    let source = "Dim a = 0.1@ + 0.2@
+Dim b = 10@ / 3
+Dim c = CCur(\"19.99\") * 3
+Dim d = 0.1@ + 0.5";
+    let mut e = run(source).unwrap();
+    let values: Vec<Object> = ["a", "b", "c", "d"]
+      .iter()
+      .map(|name| e.context.get_variable(name).unwrap())
+      .collect();
+    assert_eq!(
+      values,
+      vec![
+        Object::Currency(Currency::parse("0.3").unwrap()),
+        Object::Currency(Currency::parse("3.3333").unwrap()),
+        Object::Currency(Currency::parse("59.97").unwrap()),
+        Object::Double(0.6)
+      ]
+    );
+  }
+
+  #[test]
+  fn keywords_and_names_are_case_insensitive() {
+    let source = "dIm x As Integer = 1
+X = x + 1
+dim y = 0
+IF X = 2 THEN
+  y = foo(n:=X)
+END IF
+For i = 1 To 2
+  x = x + i
+NEXT I
+
+Function Foo(N)
+  Ret = n * 10
+End Function";
+    assert_eq!(value_of(source, "x"), Object::Integer(5));
+    assert_eq!(value_of(source, "Y"), Object::Integer(20));
+    let error = error_of("Dim Total = 1\nDim TOTAL = 2");
+    assert_eq!(
+      error,
+      RuntimeError::DuplicatedVariableDefinition(String::from("Total"))
+    );
+    assert_eq!(
+      error.to_string(),
+      "DuplicatedVariableDefinition, Total is already defined."
+    );
+  }
+
+  #[test]
+  fn procedures_shadow_builtins_of_the_same_name() {
+    let source = "Dim y = Year(\"twenty\")
+Dim r = round(2)
+
+Function Year(s As String) As String
+  Ret = s & \"!\"
+End Function
+
+Function Round(n)
+  Ret = n * 100
+End Function";
+    assert_eq!(
+      value_of(source, "y"),
+      Object::String(String::from("twenty!"))
+    );
+    assert_eq!(value_of(source, "r"), Object::Integer(200));
+  }
+
+  #[test]
+  fn unary_operators_report_the_type_of_their_operand() {
+    let procedures = "
+Function Negate(x)
+  Ret = -x
+End Function
+
+Function Identity(x)
+  Ret = +x
+End Function
+
+Function Invert(x)
+  Ret = Not x
+End Function";
+    let error = |call: &str| error_of(&format!("Dim r = {}{}", call, procedures));
+    assert_eq!(
+      error("Negate(\"a\")"),
+      RuntimeError::TypeMismatch {
+        expected: RuntimeType::Integer,
+        actual: RuntimeType::String,
+      }
+    );
+    assert_eq!(
+      error("Identity(True)"),
+      RuntimeError::TypeMismatch {
+        expected: RuntimeType::Integer,
+        actual: RuntimeType::Boolean,
+      }
+    );
+    assert_eq!(
+      error("Invert(1)"),
+      RuntimeError::TypeMismatch {
+        expected: RuntimeType::Boolean,
+        actual: RuntimeType::Integer,
+      }
+    );
+  }
 }
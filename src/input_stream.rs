@@ -1,7 +1,10 @@
+/// Reads the source one character (not byte) at a time.
+/// `current_position` and `read_position` are byte offsets into `input`,
+/// while `current_column` counts characters.
 pub struct InputStream<'a> {
   input: &'a str,
   current_position: usize,
-  current_char: u8,
+  current_char: char,
   current_line: usize,
   current_column: usize,
   file_name: String,
@@ -14,7 +17,7 @@ impl<'a> InputStream<'a> {
     let mut l = InputStream {
       input,
       current_position: 0,
-      current_char: 0,
+      current_char: '\0',
       current_line: 0,
       current_column: 0,
       file_name,
@@ -22,29 +25,58 @@ impl<'a> InputStream<'a> {
       range_start: 0,
     };
     l.next();
-    return l;
+    l.current_column = 0;
+    l
+  }
+
+  /// Reads `input` as if it started at `line` and `column` of the file, e.g. an expression
+  /// embedded in an interpolated string.
+  pub fn new_at(input: &'a str, file_name: String, line: usize, column: usize) -> Self {
+    let mut l = Self::new(input, file_name);
+    l.current_line = line;
+    l.current_column = column;
+    l
   }
 
   pub fn next(&mut self) {
-    if self.current_char == b'\n' || self.current_char == b'\r' {
+    let is_new_line =
+      self.current_char == '\n' || (self.current_char == '\r' && self.prefetch() != '\n');
+    if is_new_line {
       self.current_line += 1;
       self.current_column = 0;
     } else {
       self.current_column += 1;
     }
-    if self.read_position >= self.input.len() {
-      self.current_char = 0;
-    } else {
-      self.current_char = self.input.as_bytes()[self.read_position];
-    }
     self.current_position = self.read_position;
-    self.read_position += 1;
+    match self.input[self.read_position..].chars().next() {
+      Some(c) => {
+        self.current_char = c;
+        self.read_position += c.len_utf8();
+      }
+      None => {
+        self.current_char = '\0';
+      }
+    }
   }
 
-  pub fn current(&mut self) -> u8 {
+  pub fn current(&mut self) -> char {
     self.current_char
   }
 
+  /// Byte offset of the current character.
+  pub fn position(&self) -> usize {
+    self.current_position
+  }
+
+  pub fn slice(&self, start: usize, end: usize) -> String {
+    self.input.get(start..end).unwrap().to_string()
+  }
+
+  /// The rest of the input from the current character.
+  pub fn rest(&self) -> &'a str {
+    &self.input[self.current_position..]
+  }
+
   pub fn current_location(&self) -> (String, usize, usize) {
     (
       self.file_name.clone(),
@@ -53,22 +85,34 @@ impl<'a> InputStream<'a> {
     )
   }
 
-  pub fn prefetch(&mut self) -> u8 {
-    if self.read_position >= self.input.len() {
-      return 0;
-    } else {
-      return self.input.as_bytes()[self.read_position];
-    }
+  pub fn prefetch(&mut self) -> char {
+    self.input[self.read_position..]
+      .chars()
+      .next()
+      .unwrap_or('\0')
+  }
+
+  /// Looks ahead `n` characters past `prefetch`, i.e. `prefetch_nth(0) == prefetch()`.
+  pub fn prefetch_nth(&mut self, n: usize) -> char {
+    self.input[self.read_position..]
+      .chars()
+      .nth(n)
+      .unwrap_or('\0')
   }
 
   pub fn current_to_string(&mut self) -> String {
-    String::from_utf8(vec![self.current_char]).unwrap()
+    if self.current_char == '\0' {
+      return String::new();
+    }
+    self.current_char.to_string()
   }
 
   pub fn current_2_to_string(&mut self) -> String {
     self.start_range();
     self.next();
-    self.range_to_string()
+    let mut s = self.range_to_string();
+    s.push_str(&self.current_to_string());
+    s
   }
 
   pub fn start_range(&mut self) {
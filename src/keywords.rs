@@ -1,26 +1,51 @@
 use super::token_kind::TokenKind;
 
+/// Keywords are matched case-insensitively as in VBA, so `If`, `if` and `IF` are the same.
 pub fn get_keyword(ident: &str) -> TokenKind {
-  match ident {
-    "Const" => TokenKind::CONST,
-    "Dim" => TokenKind::DIM,
-    "And" => TokenKind::AND,
-    "Not" => TokenKind::NOT,
-    "Or" => TokenKind::OR,
-    "Xor" => TokenKind::XOR,
-    "Mod" => TokenKind::MOD,
-    "If" => TokenKind::IF,
-    "Then" => TokenKind::THEN,
-    "Else" => TokenKind::ELSE,
-    "End" => TokenKind::END,
-    "For" => TokenKind::FOR,
-    "To" => TokenKind::TO,
-    "Next" => TokenKind::NEXT,
-    "Function" => TokenKind::FUNCTION,
-    "Fn" => TokenKind::FUNCTION,
-    "True" => TokenKind::TRUE,
-    "False" => TokenKind::FALSE,   
-    "Return" => TokenKind::RETURN, 
+  match ident.to_lowercase().as_str() {
+    "const" => TokenKind::CONST,
+    "dim" => TokenKind::DIM,
+    "and" => TokenKind::AND,
+    "andalso" => TokenKind::ANDALSO,
+    "not" => TokenKind::NOT,
+    "or" => TokenKind::OR,
+    "orelse" => TokenKind::ORELSE,
+    "xor" => TokenKind::XOR,
+    "mod" => TokenKind::MOD,
+    "if" => TokenKind::IF,
+    "then" => TokenKind::THEN,
+    "else" => TokenKind::ELSE,
+    "elseif" => TokenKind::ELSEIF,
+    "end" => TokenKind::END,
+    "select" => TokenKind::SELECT,
+    "case" => TokenKind::CASE,
+    "is" => TokenKind::IS,
+    "for" => TokenKind::FOR,
+    "to" => TokenKind::TO,
+    "each" => TokenKind::EACH,
+    "in" => TokenKind::IN,
+    "step" => TokenKind::STEP,
+    "next" => TokenKind::NEXT,
+    "do" => TokenKind::DO,
+    "loop" => TokenKind::LOOP,
+    "while" => TokenKind::WHILE,
+    "until" => TokenKind::UNTIL,
+    "wend" => TokenKind::WEND,
+    "function" => TokenKind::FUNCTION,
+    "fn" => TokenKind::FUNCTION,
+    "sub" => TokenKind::SUB,
+    "byval" => TokenKind::BYVAL,
+    "byref" => TokenKind::BYREF,
+    "optional" => TokenKind::OPTIONAL,
+    "paramarray" => TokenKind::PARAMARRAY,
+    "as" => TokenKind::AS,
+    "redim" => TokenKind::REDIM,
+    "preserve" => TokenKind::PRESERVE,
+    "true" => TokenKind::TRUE,
+    "false" => TokenKind::FALSE,
+    "return" => TokenKind::RETURN,
+    "exit" => TokenKind::EXIT,
+    "continue" => TokenKind::CONTINUE,
     _ => TokenKind::IDENT,
   }
 }
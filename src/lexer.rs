@@ -2,6 +2,7 @@ use super::input_stream::InputStream;
 use super::keywords::get_keyword;
 use super::token::Token;
 use super::token_kind::TokenKind;
+use super::trivia::{Trivia, TriviaKind};
 
 pub struct Lexer<'a> {
   input_stream: InputStream<'a>,
@@ -12,49 +13,101 @@ impl<'a> Lexer<'a> {
     Lexer { input_stream }
   }
 
+  /// Reads the whole input as a lossless token stream ending with EOF.
+  /// Concatenating the leading trivia, text and trailing trivia of every token
+  /// reproduces the source exactly, which is what a formatter or highlighter needs.
+  pub fn tokenize(mut self) -> Vec<Token> {
+    let mut tokens: Vec<Token> = vec![];
+    loop {
+      let token = self.next_token();
+      let is_eof = token.kind == TokenKind::EOF;
+      tokens.push(token);
+      if is_eof {
+        return tokens;
+      }
+    }
+  }
+
   pub fn next_token(&mut self) -> Token {
-    self.skip_whitespace();
-    let token;
-    match self.input_stream.current() {
-      b'\r' => {
-        if self.input_stream.prefetch() == b'\n' {
-          token = self.new_token_with_2(TokenKind::EOL)
+    let leading_trivia = self.read_trivia(false);
+    let start = self.input_stream.position();
+    let (_, line, column) = self.input_stream.current_location();
+    let mut token = self.read_token();
+    let (_, end_line, end_column) = self.input_stream.current_location();
+    token.line = line;
+    token.column = column;
+    token.end_line = end_line;
+    token.end_column = end_column;
+    token.start = start;
+    token.end = self.input_stream.position();
+    token.text = self.input_stream.slice(start, token.end);
+    token.leading_trivia = leading_trivia;
+    if token.kind != TokenKind::EOL && token.kind != TokenKind::EOF {
+      token.trailing_trivia = self.read_trivia(true);
+    }
+    token
+  }
+
+  fn read_token(&mut self) -> Token {
+    let token = match self.input_stream.current() {
+      '\r' => {
+        if self.input_stream.prefetch() == '\n' {
+          self.new_token_with_2(TokenKind::EOL)
         } else {
-          token = self.new_token_with_1(TokenKind::EOL);
+          self.new_token_with_1(TokenKind::EOL)
         }
       }
-      b'\n' => token = self.new_token_with_1(TokenKind::EOL),
-      b',' => token = self.new_token_with_1(TokenKind::COMMA),
-      b'*' => token = self.new_token_with_1(TokenKind::ASTERISK),
-      b'%' => token = self.new_token_with_1(TokenKind::PERCENT),
-      b'/' => token = self.new_token_with_1(TokenKind::SLASH),
-      b'+' => token = self.new_token_with_1(TokenKind::PLUS),
-      b'-' => token = self.new_token_with_1(TokenKind::MINUS),
-      b'(' => token = self.new_token_with_1(TokenKind::LPAREN),
-      b')' => token = self.new_token_with_1(TokenKind::RPAREN),
-      b'=' => token = self.new_token_with_1(TokenKind::ASSIGN),
-      b'^' => token = self.new_token_with_1(TokenKind::HAT),
-      b'<' => match self.input_stream.prefetch() {
-        b'>' => token = self.new_token_with_2(TokenKind::NE),
-        b'=' => token = self.new_token_with_2(TokenKind::LE),
-        _ => token = self.new_token_with_1(TokenKind::LT),
+      '\n' => self.new_token_with_1(TokenKind::EOL),
+      ',' => self.new_token_with_1(TokenKind::COMMA),
+      ':' => match self.input_stream.prefetch() {
+        '=' => self.new_token_with_2(TokenKind::COLONEQ),
+        _ => self.new_token_with_1(TokenKind::COLON),
       },
-      b'>' => match self.input_stream.prefetch() {
-        b'=' => token = self.new_token_with_2(TokenKind::GE),
-        _ => token = self.new_token_with_1(TokenKind::GT),
+      '*' => self.new_token_with_1(TokenKind::ASTERISK),
+      '%' => self.new_token_with_1(TokenKind::PERCENT),
+      '/' => self.new_token_with_1(TokenKind::SLASH),
+      '\\' => self.new_token_with_1(TokenKind::BACKSLASH),
+      '+' => self.new_token_with_1(TokenKind::PLUS),
+      '-' => self.new_token_with_1(TokenKind::MINUS),
+      '(' => self.new_token_with_1(TokenKind::LPAREN),
+      ')' => self.new_token_with_1(TokenKind::RPAREN),
+      '=' => self.new_token_with_1(TokenKind::ASSIGN),
+      '^' => self.new_token_with_1(TokenKind::HAT),
+      '<' => match self.input_stream.prefetch() {
+        '>' => self.new_token_with_2(TokenKind::NE),
+        '=' => self.new_token_with_2(TokenKind::LE),
+        _ => self.new_token_with_1(TokenKind::LT),
       },
-      b'"' => token = self.parse_string(),
-      0 => token = self.new_token(TokenKind::EOF, String::from("")),
+      '>' => match self.input_stream.prefetch() {
+        '=' => self.new_token_with_2(TokenKind::GE),
+        _ => self.new_token_with_1(TokenKind::GT),
+      },
+      '&' => match self.input_stream.prefetch() {
+        'H' | 'h' | 'O' | 'o' | 'B' | 'b' if self.is_radix_literal() => {
+          let literal = self.read_radix_number();
+          return self.new_token(TokenKind::INT, literal);
+        }
+        _ => self.new_token_with_1(TokenKind::AMPERSAND),
+      },
+      '"' => self.parse_string(),
+      '#' => self.parse_date(),
+      '$' if self.input_stream.prefetch() == '"' => self.parse_interpolated_string(),
+      '\0' => self.new_token(TokenKind::EOF, String::from("")),
       c => {
         if Self::is_letter(&c) {
           let ident = self.read_identifier();
           let kind = get_keyword(&ident);
+          if kind == TokenKind::IDENT {
+            if let Some(suffix) = self.read_type_suffix(&['%', '&', '!', '#', '@', '$']) {
+              return self.new_token(kind, ident + &suffix);
+            }
+          }
           return self.new_token(kind, ident);
         } else if Self::is_digit(&c) {
-          let literal = self.read_number();
-          return self.new_token(TokenKind::INT, literal);
+          let (kind, literal) = self.read_number();
+          return self.new_token(kind, literal);
         } else {
-          token = self.new_token_with_1(TokenKind::ILLEGAL);
+          self.new_token_with_1(TokenKind::ILLEGAL)
         }
       }
     };
@@ -62,10 +115,95 @@ impl<'a> Lexer<'a> {
     token
   }
 
-  fn skip_whitespace(&mut self) {
+  /// Reads whitespace, comments and line continuations.
+  /// Trailing trivia ends with the first line continuation, so the trivia of the next line
+  /// becomes leading trivia of the next token.
+  fn read_trivia(&mut self, trailing: bool) -> Vec<Trivia> {
+    let mut trivia: Vec<Trivia> = vec![];
+    loop {
+      let start = self.input_stream.position();
+      let after_space = matches!(trivia.last(), Some(t) if t.kind == TriviaKind::Whitespace);
+      let kind = match self.input_stream.current() {
+        ' ' | '\t' => {
+          while matches!(self.input_stream.current(), ' ' | '\t') {
+            self.input_stream.next();
+          }
+          TriviaKind::Whitespace
+        }
+        '_' if after_space && self.is_line_continuation() => {
+          self.skip_line_continuation();
+          TriviaKind::LineContinuation
+        }
+        '\'' => {
+          self.skip_comment();
+          if self
+            .input_stream
+            .slice(start, self.input_stream.position())
+            .starts_with("'''")
+          {
+            TriviaKind::DocComment
+          } else {
+            TriviaKind::Comment
+          }
+        }
+        _ if self.is_rem_comment() => {
+          self.skip_comment();
+          TriviaKind::Comment
+        }
+        _ => return trivia,
+      };
+      let end = self.input_stream.position();
+      trivia.push(Trivia {
+        kind,
+        text: self.input_stream.slice(start, end),
+        start,
+        end,
+      });
+      if trailing && kind == TriviaKind::LineContinuation {
+        return trivia;
+      }
+    }
+  }
+
+  /// ` _` followed only by spaces up to the end of line joins the next line to the current one.
+  fn is_line_continuation(&mut self) -> bool {
+    let mut i = 0;
+    loop {
+      match self.input_stream.prefetch_nth(i) {
+        ' ' | '\t' => i += 1,
+        c => return c == '\r' || c == '\n',
+      }
+    }
+  }
+
+  fn skip_line_continuation(&mut self) {
+    loop {
+      match self.input_stream.current() {
+        '\r' => {
+          if self.input_stream.prefetch() == '\n' {
+            self.input_stream.next();
+          }
+          break;
+        }
+        '\n' => break,
+        _ => self.input_stream.next(),
+      }
+    }
+    self.input_stream.next();
+  }
+
+  /// `Rem` starts a comment, but `Remainder` is an identifier.
+  fn is_rem_comment(&self) -> bool {
+    let mut chars = self.input_stream.rest().chars();
+    let keyword: String = chars.by_ref().take(3).collect();
+    keyword.to_lowercase() == "rem" && !chars.next().is_some_and(|c| Self::is_identifier_part(&c))
+  }
+
+  /// Skips up to (but not including) the end of line so that the EOL token is kept.
+  fn skip_comment(&mut self) {
     loop {
       let c = self.input_stream.current();
-      if !(c == b' ' || c == b'\t') {
+      if c == '\r' || c == '\n' || c == '\0' {
         break;
       }
       self.input_stream.next();
@@ -76,7 +214,7 @@ impl<'a> Lexer<'a> {
     self.input_stream.start_range();
     loop {
       let c = self.input_stream.current();
-      if !(Self::is_digit(&c) || Self::is_letter(&c)) {
+      if !Self::is_identifier_part(&c) {
         break;
       }
       self.input_stream.next();
@@ -84,45 +222,175 @@ impl<'a> Lexer<'a> {
     self.input_stream.range_to_string()
   }
 
+  /// Reads a string literal. `""` inside the literal stands for a single `"`.
   fn parse_string(&mut self) -> Token {
     self.input_stream.next();
-    self.input_stream.start_range();
+    let mut value = String::new();
     loop {
       let c = self.input_stream.current();
-      if c == b'"' {
-        break;
+      if c == '"' {
+        if self.input_stream.prefetch() != '"' {
+          break;
+        }
+        self.input_stream.next();
       }
-      if c == 0 {
+      if c == '\0' {
         return self.new_token_with_1(TokenKind::ILLEGAL);
       }
+      value.push(c);
       self.input_stream.next();
     }
-    self.new_token_by_range(TokenKind::STRING)
+    self.new_token(TokenKind::STRING, value)
   }
 
-  fn read_number(&mut self) -> String {
+  /// Reads `$"..."` and keeps the text between the quotes as it is, so that the parser can
+  /// split it into text and `{expression}` parts. Quotes inside braces belong to a string
+  /// literal of the embedded expression.
+  fn parse_interpolated_string(&mut self) -> Token {
+    self.input_stream.next();
+    self.input_stream.next();
     self.input_stream.start_range();
+    let mut depth = 0;
     loop {
       let c = self.input_stream.current();
-      if !Self::is_digit(&c) {
-        break;
+      match c {
+        '\0' => return self.new_token_with_1(TokenKind::ILLEGAL),
+        '"' if depth == 0 => {
+          if self.input_stream.prefetch() != '"' {
+            break;
+          }
+          self.input_stream.next();
+        }
+        '"' => loop {
+          self.input_stream.next();
+          match self.input_stream.current() {
+            '\0' => return self.new_token_with_1(TokenKind::ILLEGAL),
+            '"' if self.input_stream.prefetch() == '"' => self.input_stream.next(),
+            '"' => break,
+            _ => {}
+          }
+        },
+        '{' | '}' if depth == 0 && self.input_stream.prefetch() == c => self.input_stream.next(),
+        '{' => depth += 1,
+        '}' if depth > 0 => depth -= 1,
+        _ => {}
       }
       self.input_stream.next();
     }
-    self.input_stream.range_to_string()
+    self.new_token_by_range(TokenKind::INTERPOLATION)
   }
 
-  fn is_letter(ch: &u8) -> bool {
-    let ch = char::from(*ch);
-    'a' <= ch && ch <= 'z' || 'A' <= ch && ch <= 'Z' || ch == '_'
+  /// Reads `#2026-10-18#` and keeps the text between `#`s.
+  fn parse_date(&mut self) -> Token {
+    self.input_stream.next();
+    self.input_stream.start_range();
+    loop {
+      match self.input_stream.current() {
+        '#' => break,
+        '\r' | '\n' | '\0' => return self.new_token_with_1(TokenKind::ILLEGAL),
+        _ => self.input_stream.next(),
+      }
+    }
+    self.new_token_by_range(TokenKind::DATE)
   }
 
-  fn is_digit(ch: &u8) -> bool {
-    let ch = char::from(*ch);
-    return '0' <= ch && ch <= '9';
+  /// Reads an integer or a floating-point literal as described in SYNTAX.md,
+  /// e.g. `10`, `1.5`, `2e10`, `3.0D`, `19.99@`.
+  fn read_number(&mut self) -> (TokenKind, String) {
+    self.input_stream.start_range();
+    let mut kind = TokenKind::INT;
+    self.skip_digits();
+    if self.input_stream.current() == '.' {
+      kind = TokenKind::FLOAT;
+      self.input_stream.next();
+      self.skip_digits();
+    }
+    if self.input_stream.current() == 'e' || self.input_stream.current() == 'E' {
+      let c = self.input_stream.prefetch();
+      let has_exponent = Self::is_digit(&c)
+        || ((c == '+' || c == '-') && Self::is_digit(&self.input_stream.prefetch_nth(1)));
+      if has_exponent {
+        kind = TokenKind::FLOAT;
+        self.input_stream.next();
+        self.input_stream.next();
+        self.skip_digits();
+      }
+    }
+    match self.input_stream.current() {
+      'f' | 'F' | 'd' | 'D' if !Self::is_identifier_part(&self.input_stream.prefetch()) => {
+        kind = TokenKind::FLOAT;
+        self.input_stream.next();
+      }
+      _ => match self.read_type_suffix(&['%', '&', '!', '#', '@']).as_deref() {
+        Some("!") | Some("#") => kind = TokenKind::FLOAT,
+        Some("@") => kind = TokenKind::CURRENCY,
+        _ => {}
+      },
+    }
+    (kind, self.input_stream.range_to_string())
+  }
+
+  fn is_radix_literal(&mut self) -> bool {
+    let digit = self.input_stream.prefetch_nth(1);
+    match self.input_stream.prefetch() {
+      'H' | 'h' => digit.is_ascii_hexdigit(),
+      'O' | 'o' => ('0'..='7').contains(&digit),
+      _ => digit == '0' || digit == '1',
+    }
   }
 
-  fn new_token(&self, kind: TokenKind, value: String) -> Token {
+  /// Reads `&HFF`, `&O17` or `&B1010` with an optional `%` or `&` suffix.
+  /// Only the digits of the radix are read, so `&B102` is `&B10` followed by `2`.
+  fn read_radix_number(&mut self) -> String {
+    self.input_stream.start_range();
+    self.input_stream.next();
+    let radix = match self.input_stream.current() {
+      'H' | 'h' => 16,
+      'O' | 'o' => 8,
+      _ => 2,
+    };
+    self.input_stream.next();
+    while self.input_stream.current().is_digit(radix) {
+      self.input_stream.next();
+    }
+    let literal = self.input_stream.range_to_string();
+    match self.read_type_suffix(&['%', '&']) {
+      Some(suffix) => literal + &suffix,
+      None => literal,
+    }
+  }
+
+  /// Reads a type suffix such as `x$` or `10&` which is directly attached to the previous
+  /// characters. `a%b` is still read as `a % b`.
+  fn read_type_suffix(&mut self, suffixes: &[char]) -> Option<String> {
+    let c = self.input_stream.current();
+    if suffixes.contains(&c) && !Self::is_identifier_part(&self.input_stream.prefetch()) {
+      self.input_stream.next();
+      Some(c.to_string())
+    } else {
+      None
+    }
+  }
+
+  fn skip_digits(&mut self) {
+    while Self::is_digit(&self.input_stream.current()) {
+      self.input_stream.next();
+    }
+  }
+
+  fn is_identifier_part(ch: &char) -> bool {
+    Self::is_letter(ch) || Self::is_digit(ch)
+  }
+
+  fn is_letter(ch: &char) -> bool {
+    ch.is_alphabetic() || *ch == '_'
+  }
+
+  fn is_digit(ch: &char) -> bool {
+    ch.is_ascii_digit()
+  }
+
+  fn new_token(&mut self, kind: TokenKind, value: String) -> Token {
     let (file_name, line, column) = self.input_stream.current_location();
 
     Token {
@@ -131,6 +399,13 @@ impl<'a> Lexer<'a> {
       file_name,
       line,
       column,
+      end_line: line,
+      end_column: column,
+      start: 0,
+      end: 0,
+      text: String::new(),
+      leading_trivia: vec![],
+      trailing_trivia: vec![],
     }
   }
 
@@ -149,3 +424,211 @@ impl<'a> Lexer<'a> {
     self.new_token(kind, value)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// The kinds and values of the tokens in `source` without the final EOF.
+  fn tokens(source: &str) -> Vec<(TokenKind, String)> {
+    let mut tokens: Vec<(TokenKind, String)> =
+      Lexer::new(InputStream::new(source, String::from("test.bs")))
+        .tokenize()
+        .into_iter()
+        .map(|t| (t.kind, t.value))
+        .collect();
+    tokens.pop();
+    tokens
+  }
+
+  #[test]
+  fn radix_literals_read_only_their_digits() {
+    assert_eq!(
+      tokens("&HFF&"),
+      vec![(TokenKind::INT, String::from("&HFF&"))]
+    );
+    assert_eq!(tokens("&O17"), vec![(TokenKind::INT, String::from("&O17"))]);
+    assert_eq!(
+      tokens("&B102"),
+      vec![
+        (TokenKind::INT, String::from("&B10")),
+        (TokenKind::INT, String::from("2"))
+      ]
+    );
+    assert_eq!(
+      tokens("&O19"),
+      vec![
+        (TokenKind::INT, String::from("&O1")),
+        (TokenKind::INT, String::from("9"))
+      ]
+    );
+  }
+
+  #[test]
+  fn reads_utf8_characters() {
+    assert_eq!(
+      tokens("Dim 名前 = \"héllo 🌍\""),
+      vec![
+        (TokenKind::DIM, String::from("Dim")),
+        (TokenKind::IDENT, String::from("名前")),
+        (TokenKind::ASSIGN, String::from("=")),
+        (TokenKind::STRING, String::from("héllo 🌍"))
+      ]
+    );
+  }
+
+  #[test]
+  fn columns_count_characters() {
+    let source = "é = \"🌍\"\r\ny";
+    let tokens = Lexer::new(InputStream::new(source, String::from("test.bs"))).tokenize();
+    let string = &tokens[2];
+    assert_eq!((string.line, string.column, string.end_column), (0, 4, 7));
+    let y = &tokens[4];
+    assert_eq!((y.kind, y.line, y.column), (TokenKind::IDENT, 1, 0));
+  }
+
+  /// Concatenates the trivia and the text of every token.
+  fn reproduce(source: &str) -> String {
+    let mut text = String::new();
+    for t in Lexer::new(InputStream::new(source, String::from("test.bs"))).tokenize() {
+      for trivia in t.leading_trivia.iter() {
+        assert_eq!(&source[trivia.start..trivia.end], trivia.text);
+        text += &trivia.text;
+      }
+      assert_eq!(&source[t.start..t.end], t.text);
+      text += &t.text;
+      for trivia in t.trailing_trivia.iter() {
+        assert_eq!(&source[trivia.start..trivia.end], trivia.text);
+        text += &trivia.text;
+      }
+    }
+    text
+  }
+
+  #[test]
+  fn tokens_reproduce_the_source() {
+    let source = "''' Adds one.\r
+Function Inc(ByRef x As Integer) ' comment\r
+  x = x + _\r
+    1  \t\r
+End Function\r
+Rem done\r
+Print($\"{\"a\"\"b\"} {#2026-10-18#}\")";
+    assert_eq!(reproduce(source), source);
+    assert_eq!(reproduce(""), "");
+    assert_eq!(
+      reproduce("  \n\n' only a comment"),
+      "  \n\n' only a comment"
+    );
+  }
+
+  #[test]
+  fn trivia_is_attached_to_tokens() {
+    let source = "x = 1 ' one\n";
+    let tokens = Lexer::new(InputStream::new(source, String::from("test.bs"))).tokenize();
+    let one = &tokens[2];
+    assert_eq!(one.text, "1");
+    let kinds: Vec<TriviaKind> = one.trailing_trivia.iter().map(|t| t.kind).collect();
+    assert_eq!(kinds, vec![TriviaKind::Whitespace, TriviaKind::Comment]);
+    assert_eq!(one.trailing_trivia[1].text, "' one");
+  }
+
+  #[test]
+  fn offsets_count_bytes() {
+    let source = "x = \"é\"\r\ny";
+    let tokens = Lexer::new(InputStream::new(source, String::from("test.bs"))).tokenize();
+    let string = &tokens[2];
+    assert_eq!((string.start, string.end), (4, 8));
+    assert_eq!(&source[string.start..string.end], "\"é\"");
+    let y = &tokens[4];
+    assert_eq!((y.start, y.end), (10, 11));
+    assert_eq!(&source[y.start..y.end], "y");
+  }
+
+  #[test]
+  fn comments_are_trivia() {
+    let source = "''' doc\nx = 1 ' note\nRem whole line\nRemark = \"it's\" Rem trailing\n";
+    assert_eq!(
+      tokens(source),
+      vec![
+        (TokenKind::EOL, String::from("\n")),
+        (TokenKind::IDENT, String::from("x")),
+        (TokenKind::ASSIGN, String::from("=")),
+        (TokenKind::INT, String::from("1")),
+        (TokenKind::EOL, String::from("\n")),
+        (TokenKind::EOL, String::from("\n")),
+        (TokenKind::IDENT, String::from("Remark")),
+        (TokenKind::ASSIGN, String::from("=")),
+        (TokenKind::STRING, String::from("it's")),
+        (TokenKind::EOL, String::from("\n"))
+      ]
+    );
+    let all = Lexer::new(InputStream::new(source, String::from("test.bs"))).tokenize();
+    let comments: Vec<(TriviaKind, &str)> = all
+      .iter()
+      .flat_map(|t| t.leading_trivia.iter().chain(t.trailing_trivia.iter()))
+      .filter(|t| t.kind != TriviaKind::Whitespace)
+      .map(|t| (t.kind, t.text.as_str()))
+      .collect();
+    assert_eq!(
+      comments,
+      vec![
+        (TriviaKind::DocComment, "''' doc"),
+        (TriviaKind::Comment, "' note"),
+        (TriviaKind::Comment, "Rem whole line"),
+        (TriviaKind::Comment, "Rem trailing")
+      ]
+    );
+  }
+
+  #[test]
+  fn floating_point_literals() {
+    let source = "1.5 2e10 3.0D 4F 5. 1.5e-3 2! 7#";
+    let kinds: Vec<TokenKind> = tokens(source).into_iter().map(|(k, _)| k).collect();
+    assert_eq!(kinds, vec![TokenKind::FLOAT; 8]);
+    assert_eq!(
+      tokens("1.5e"),
+      vec![
+        (TokenKind::FLOAT, String::from("1.5")),
+        (TokenKind::IDENT, String::from("e"))
+      ]
+    );
+  }
+
+  #[test]
+  fn doubled_quotes_escape_a_quote() {
+    let source = "\"say \"\"hi\"\"\" \"\"";
+    assert_eq!(
+      tokens(source),
+      vec![
+        (TokenKind::STRING, String::from("say \"hi\"")),
+        (TokenKind::STRING, String::new())
+      ]
+    );
+    let kinds: Vec<TokenKind> = tokens("$\"{\"}\"}\" \"open")
+      .into_iter()
+      .map(|(k, _)| k)
+      .collect();
+    assert_eq!(kinds, vec![TokenKind::INTERPOLATION, TokenKind::ILLEGAL]);
+  }
+
+  #[test]
+  fn line_continuation_joins_lines() {
+    assert_eq!(
+      tokens("x = a_ + _\r\n  2 _\n"),
+      vec![
+        (TokenKind::IDENT, String::from("x")),
+        (TokenKind::ASSIGN, String::from("=")),
+        (TokenKind::IDENT, String::from("a_")),
+        (TokenKind::PLUS, String::from("+")),
+        (TokenKind::INT, String::from("2"))
+      ]
+    );
+    let source = "a: b";
+    let kinds: Vec<TokenKind> = tokens(source).into_iter().map(|(k, _)| k).collect();
+    assert_eq!(
+      kinds,
+      vec![TokenKind::IDENT, TokenKind::COLON, TokenKind::IDENT]
+    );
+  }
+}
@@ -1,4 +1,7 @@
+mod array;
 mod ast;
+mod currency;
+mod date;
 mod executor;
 mod input_stream;
 mod keywords;
@@ -9,12 +12,14 @@ mod parser;
 mod runtime_error;
 mod token;
 mod token_kind;
+mod trivia;
+mod type_checker;
 extern crate log;
 mod context;
-use env_logger;
 use std::env;
 use std::fs::File;
 use std::io::prelude::*;
+use std::process;
 
 fn main() {
   env::set_var("RUST_LOG", "info");
@@ -23,33 +28,57 @@ fn main() {
   let args: Vec<String> = env::args().collect();
   println!("B# version 0.0");
   println!("{:?}", args);
-  let src = &args[1];
+  let (dump_tokens, src) = match args.get(1..).unwrap_or(&[]) {
+    [flag, src] if flag == "--tokens" => (true, src),
+    [src] if src != "--tokens" => (false, src),
+    _ => {
+      eprintln!("Usage: bsharp-lang [--tokens] <file>");
+      process::exit(2);
+    }
+  };
   let mut f = File::open(src).expect("file not found");
 
   let mut contents = String::new();
   f.read_to_string(&mut contents)
     .expect("something went wrong reading the file");
   println!("Sourcecode:\n\n{}", contents);
-  let mut e = executor::Executor::new();
+  if dump_tokens {
+    let i = input_stream::InputStream::new(&contents, String::from(src));
+    for t in lexer::Lexer::new(i).tokenize() {
+      println!("{}", t);
+    }
+    return;
+  }
+  // BSHARP_NOW=2026-10-18 fixes the clock read by `Now` so that runs are deterministic.
+  let mut e = match env::var("BSHARP_NOW") {
+    Ok(now) => {
+      let now = date::Date::parse(&now).expect("BSHARP_NOW is not a date");
+      executor::Executor::with_clock(Box::new(date::FixedClock(now)))
+    }
+    Err(_) => executor::Executor::new(),
+  };
   let i = input_stream::InputStream::new(&contents, String::from(src));
   let l = lexer::Lexer::new(i);
   let mut parser = parser::Parser::new(l);
-  let program = parser.parse_program();
-  match program {
-    Ok(p) => {
-      println!("{:#?}", p);
-      let r = e.execute(&p);
-      match r {
-        Ok(r) => {
-          println!("Result: {}", r);
-        }
-        Err(e) => {
-          println!("Execution error: {}", e);
-        }
-      }
+  let (program, mut errors) = parser.parse_program();
+  if errors.is_empty() {
+    errors = type_checker::TypeChecker::new().check(&program);
+  }
+  if !errors.is_empty() {
+    for e in errors.iter() {
+      println!("Compile error: {}", e);
+    }
+    println!("{} compile error(s)", errors.len());
+    return;
+  }
+  println!("{:#?}", program);
+  let r = e.execute(&program);
+  match r {
+    Ok(r) => {
+      println!("Result: {}", r);
     }
     Err(e) => {
-      println!("Compile error: {}", e);
+      println!("Execution error: {}", e);
     }
   }
 }
@@ -1,13 +1,24 @@
+use super::array::Array;
 use super::ast::function::FunctionDeclaration;
+use super::ast::operator::BinaryOperator;
+use super::currency::Currency;
+use super::date::Date;
 use super::runtime_error::RuntimeError;
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive};
 use std::fmt;
 use std::rc::Rc;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum RuntimeType {
   Integer,
+  BigInteger,
+  Double,
+  Currency,
+  Date,
   Boolean,
   String,
+  Array,
   Function,
   Undefined,
 }
@@ -17,20 +28,78 @@ impl fmt::Display for RuntimeType {
   }
 }
 
+impl RuntimeType {
+  /// Value of a variable declared with `As` but without an initializer.
+  /// A Date starts at 1899-12-30 as in VBA.
+  pub fn default_value(&self) -> Object {
+    match self {
+      RuntimeType::Integer => Object::Integer(0),
+      RuntimeType::BigInteger => Object::BigInteger(BigInt::from(0)),
+      RuntimeType::Double => Object::Double(0.0),
+      RuntimeType::Currency => Object::Currency(Currency::from(0)),
+      RuntimeType::Date => Object::Date(Date::new(1899, 12, 30, 0, 0, 0).unwrap()),
+      RuntimeType::Boolean => Object::Boolean(false),
+      RuntimeType::String => Object::String(String::new()),
+      RuntimeType::Array => Object::Array(Array::empty(None)),
+      RuntimeType::Function | RuntimeType::Undefined => Object::Undefined,
+    }
+  }
+
+  /// Whether a value of type `other` can be stored into a variable of this type.
+  /// Integer widens to BigInteger, Double and Currency, but not the other way around.
+  /// A BigInteger narrows to Integer, which overflows if it is out of range.
+  pub fn accepts(&self, other: &RuntimeType) -> bool {
+    self == other
+      || (*other == RuntimeType::Integer
+        && matches!(
+          self,
+          RuntimeType::BigInteger | RuntimeType::Double | RuntimeType::Currency
+        ))
+      || (*self == RuntimeType::Integer && *other == RuntimeType::BigInteger)
+  }
+}
+
 pub trait TypeOf {
   fn type_of(&self) -> RuntimeType;
 }
 
 pub trait Add {
-  fn add(&self, offset: i32) -> Result<Object, RuntimeError>;
+  /// Adds two numbers, promoting Integer to Double when they are mixed.
+  /// An Integer sum out of range is promoted to BigInteger, and a BigInteger sum which fits
+  /// is an Integer again. An infinite Double is an Overflow.
+  fn add(&self, other: &Object) -> Result<Object, RuntimeError>;
+}
+
+pub trait Coerce {
+  /// Converts the value to be stored into a variable declared `As to`.
+  fn coerce(&self, to: &RuntimeType) -> Result<Object, RuntimeError>;
+}
+
+pub trait ToText {
+  /// Converts the value to a String as `&` does. Booleans are `True` or `False`, and
+  /// Undefined is an empty string.
+  fn to_text(&self) -> Result<String, RuntimeError>;
+}
+
+pub trait Iterate {
+  /// Elements visited by `For Each`. A String is visited one character at a time.
+  fn iterate(&self) -> Result<Vec<Object>, RuntimeError>;
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Object {
   Undefined,
   Integer(i32),
+  /// An integer of any size, e.g. an Integer result that overflowed or `CBigInt("1E30")`.
+  BigInteger(BigInt),
+  Double(f64),
+  /// A fixed-point number with 4 decimal places such as `19.99@`.
+  Currency(Currency),
+  Date(Date),
   Boolean(bool),
   String(String),
+  /// An array declared by `Dim a(10)` or the arguments taken by a `ParamArray` parameter.
+  Array(Array),
   Function(Rc<FunctionDeclaration>),
 }
 impl fmt::Display for Object {
@@ -38,8 +107,16 @@ impl fmt::Display for Object {
     match self {
       Object::Undefined => write!(fmt, "[Undefined]"),
       Object::Integer(v) => write!(fmt, "{}", v),
+      Object::BigInteger(v) => write!(fmt, "{}", v),
+      Object::Double(v) => write!(fmt, "{}", v),
+      Object::Currency(v) => write!(fmt, "{}", v),
+      Object::Date(d) => write!(fmt, "{}", d),
       Object::Boolean(b) => write!(fmt, "[Boolean] ({})", b),
       Object::String(s) => write!(fmt, "{}", s),
+      Object::Array(a) => {
+        let items: Vec<String> = a.items().iter().map(|o| o.to_string()).collect();
+        write!(fmt, "[Array] ({})", items.join(", "))
+      }
       Object::Function(f) => write!(fmt, "[Function] (\"{}\")", f.identifier),
     }
   }
@@ -49,21 +126,136 @@ impl TypeOf for Object {
     match self {
       Object::Undefined => RuntimeType::Undefined,
       Object::Integer(_) => RuntimeType::Integer,
+      Object::BigInteger(_) => RuntimeType::BigInteger,
+      Object::Double(_) => RuntimeType::Double,
+      Object::Currency(_) => RuntimeType::Currency,
+      Object::Date(_) => RuntimeType::Date,
       Object::Boolean(_) => RuntimeType::Boolean,
       Object::String(_) => RuntimeType::String,
+      Object::Array(_) => RuntimeType::Array,
       Object::Function(_) => RuntimeType::Function,
     }
   }
 }
 impl Add for Object {
-  fn add(&self, offset: i32) -> Result<Object, RuntimeError> {
-    let actual = self.type_of();
+  fn add(&self, other: &Object) -> Result<Object, RuntimeError> {
+    let overflow = || RuntimeError::Overflow(BinaryOperator::ADD, self.clone(), other.clone());
+    let currency = |sum: Option<Currency>| sum.map(Object::Currency).ok_or_else(overflow);
+    let sum = match (self, other) {
+      (Object::Integer(l), Object::Integer(r)) => {
+        return Ok(match l.checked_add(*r) {
+          Some(n) => Object::Integer(n),
+          None => Object::BigInteger(BigInt::from(*l) + r),
+        });
+      }
+      (Object::BigInteger(l), Object::BigInteger(r)) => return Ok(integer(l + r)),
+      (Object::BigInteger(l), Object::Integer(r)) => return Ok(integer(l + r)),
+      (Object::Integer(l), Object::BigInteger(r)) => return Ok(integer(l + r)),
+      (Object::Currency(l), Object::Currency(r)) => return currency(l.checked_add(r)),
+      (Object::Currency(l), Object::Integer(r)) => return currency(l.checked_add(&(*r).into())),
+      (Object::Integer(l), Object::Currency(r)) => return currency(r.checked_add(&(*l).into())),
+      (Object::Currency(l), Object::Double(r)) => l.to_double() + r,
+      (Object::Double(l), Object::Currency(r)) => l + r.to_double(),
+      (Object::Integer(l), Object::Double(r)) => *l as f64 + r,
+      (Object::Double(l), Object::Integer(r)) => l + *r as f64,
+      (Object::BigInteger(l), Object::Double(r)) => to_double(l) + r,
+      (Object::Double(l), Object::BigInteger(r)) => l + to_double(r),
+      (Object::Double(l), Object::Double(r)) => l + r,
+      (Object::Integer(_) | Object::BigInteger(_) | Object::Double(_) | Object::Currency(_), r) => {
+        return Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: r.type_of(),
+        })
+      }
+      (l, _) => {
+        return Err(RuntimeError::TypeMismatch {
+          expected: RuntimeType::Integer,
+          actual: l.type_of(),
+        })
+      }
+    };
+    if sum.is_infinite() {
+      return Err(overflow());
+    }
+    Ok(Object::Double(sum))
+  }
+}
+impl ToText for Object {
+  fn to_text(&self) -> Result<String, RuntimeError> {
+    match self {
+      Object::Undefined => Ok(String::new()),
+      Object::Boolean(true) => Ok(String::from("True")),
+      Object::Boolean(false) => Ok(String::from("False")),
+      Object::Integer(_)
+      | Object::BigInteger(_)
+      | Object::Double(_)
+      | Object::Currency(_)
+      | Object::Date(_)
+      | Object::String(_) => Ok(self.to_string()),
+      o => Err(RuntimeError::TypeMismatch {
+        expected: RuntimeType::String,
+        actual: o.type_of(),
+      }),
+    }
+  }
+}
+impl Iterate for Object {
+  fn iterate(&self) -> Result<Vec<Object>, RuntimeError> {
     match self {
-      Object::Integer(n) => Ok(Object::Integer(n + offset)),
-      _ => Err(RuntimeError::TypeMismatch {
-        expected: RuntimeType::Integer,
-        actual,
+      Object::String(s) => Ok(s.chars().map(|c| Object::String(c.to_string())).collect()),
+      Object::Array(a) => Ok(a.items().to_vec()),
+      o => Err(RuntimeError::NotIterable(o.type_of())),
+    }
+  }
+}
+impl Coerce for Object {
+  fn coerce(&self, to: &RuntimeType) -> Result<Object, RuntimeError> {
+    match (self, to) {
+      (Object::Integer(n), RuntimeType::Double) => Ok(Object::Double(*n as f64)),
+      (Object::Integer(n), RuntimeType::BigInteger) => Ok(Object::BigInteger(BigInt::from(*n))),
+      (Object::BigInteger(n), RuntimeType::Integer) => match n.to_i32() {
+        Some(n) => Ok(Object::Integer(n)),
+        None => Err(RuntimeError::ConversionOverflow {
+          value: self.clone(),
+          to: *to,
+        }),
+      },
+      (Object::Integer(n), RuntimeType::Currency) => Ok(Object::Currency(Currency::from(*n))),
+      (o, t) if t.accepts(&o.type_of()) => Ok(o.clone()),
+      (o, t) => Err(RuntimeError::TypeMismatch {
+        expected: *t,
+        actual: o.type_of(),
       }),
     }
   }
 }
+
+/// Makes an Integer of `n` if it fits, or a BigInteger otherwise.
+pub fn integer(n: BigInt) -> Object {
+  match n.to_i32() {
+    Some(n) => Object::Integer(n),
+    None => Object::BigInteger(n),
+  }
+}
+
+/// Converts a BigInteger to the nearest Double, which is infinite when it is out of range.
+pub fn to_double(n: &BigInt) -> f64 {
+  match n.to_f64() {
+    Some(d) => d,
+    None if n.is_negative() => f64::NEG_INFINITY,
+    None => f64::INFINITY,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn big_integers_out_of_range_are_infinite_doubles() {
+    let huge = BigInt::from(10).pow(400);
+    assert_eq!(to_double(&huge), f64::INFINITY);
+    assert_eq!(to_double(&-huge), f64::NEG_INFINITY);
+    assert_eq!(to_double(&BigInt::from(-3)), -3.0);
+  }
+}
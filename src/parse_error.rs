@@ -5,6 +5,8 @@ use std::fmt;
 pub enum ParseErrorType {
   InvalidToken,
   Unsupported,
+  /// Reported by the type checker before the program runs.
+  TypeMismatch,
 }
 impl fmt::Display for ParseErrorType {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -1,48 +1,92 @@
 use super::ast::{
-  expression::Expression, function::FunctionDeclaration, operator::BinaryOperator,
-  operator::UnaryOperator, program::Program, statement::Statement,
+  expression::Arguments, expression::Expression, expression::ExpressionKind,
+  expression::InterpolationPart, function::FunctionDeclaration, function::Parameter,
+  function::PassingMode, function::ProcedureKind, operator::BinaryOperator,
+  operator::UnaryOperator, program::Program, span::Span, statement::ArrayBound,
+  statement::BlockKind, statement::CaseCondition, statement::LoopCondition, statement::Statement,
+  statement::StatementKind,
 };
+use super::currency::Currency;
+use super::date::Date;
+use super::input_stream::InputStream;
 use super::lexer::Lexer;
+use super::object::RuntimeType;
 use super::parse_error::{ParseError, ParseErrorType};
 use super::token::Token;
 use super::token_kind::TokenKind;
 use log::debug;
+use num_bigint::BigInt;
+
+/// Precedence of operators from the loosest to the tightest as in VB.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+enum Precedence {
+  Lowest,
+  Xor,
+  Or,
+  And,
+  Not,
+  Comparison,
+  Concatenation,
+  Additive,
+  Modulus,
+  IntegerDivision,
+  Multiplicative,
+  Unary,
+  Exponential,
+}
 
 pub struct Parser<'a> {
   lexer: Lexer<'a>,
   current_token: Token,
   next_token: Token,
+  /// End location of the last consumed token, where the span of a node ends.
+  previous_end: (usize, usize),
+  /// Blocks enclosing the current statement, which `Exit` and `Continue` may refer to.
+  blocks: Vec<BlockKind>,
+  errors: Vec<ParseError>,
 }
 
 impl<'a> Parser<'a> {
   pub fn new(mut lexer: Lexer<'a>) -> Self {
     let current_token = lexer.next_token();
     let next_token = lexer.next_token();
-    return Parser {
+    Parser {
       lexer,
       current_token,
       next_token,
-    };
+      previous_end: (0, 0),
+      blocks: vec![],
+      errors: vec![],
+    }
   }
 
-  pub fn parse_program(&mut self) -> Result<Program, ParseError> {
+  /// Parses the whole program. Errors do not stop the parse; the parser resynchronises
+  /// and returns what it could parse together with every error found.
+  pub fn parse_program(&mut self) -> (Program, Vec<ParseError>) {
     debug!(">>> parse_program");
     let mut statements: Vec<Statement> = vec![];
     let mut functions: Vec<FunctionDeclaration> = vec![];
     loop {
       match self.current_token.kind {
         TokenKind::EOF => break,
-        TokenKind::FUNCTION => {
-          let f = self.parse_function_declaration()?;
-          functions.push(f);
-        }
+        TokenKind::FUNCTION | TokenKind::SUB => match self.parse_function_declaration() {
+          Ok(f) => functions.push(f),
+          Err(e) => {
+            self.errors.push(e);
+            self.synchronize();
+          }
+        },
         _ => {
-          let mut add = self.parse_statements(|k| match *k {
-            TokenKind::EOF => true,
-            TokenKind::FUNCTION => true,
-            _ => false,
-          })?;
+          let to_stop: fn(&TokenKind) -> bool =
+            |k| matches!(*k, TokenKind::EOF | TokenKind::FUNCTION | TokenKind::SUB);
+          let mut add = self.parse_statements(to_stop);
           statements.append(&mut add);
+          let k = self.current_token.kind;
+          if !to_stop(&k) {
+            let e = self.raise_error(ParseErrorType::InvalidToken, format!("Unexpected {}", k));
+            self.errors.push(e);
+            self.skip_line();
+          }
         }
       }
     }
@@ -51,16 +95,62 @@ impl<'a> Parser<'a> {
       functions,
     };
     debug!("<<< parse_program: {}", program);
-    Ok(program)
+    (program, std::mem::take(&mut self.errors))
   }
 
   fn parse_function_declaration(&mut self) -> Result<FunctionDeclaration, ParseError> {
     debug!(">>> parse_function_declaration {:?}", self.current_token);
+    let start = self.current_span();
+    let (kind, block, end_keyword) = match self.current_token.kind {
+      TokenKind::SUB => (ProcedureKind::Sub, BlockKind::Sub, TokenKind::SUB),
+      _ => (
+        ProcedureKind::Function,
+        BlockKind::Function,
+        TokenKind::FUNCTION,
+      ),
+    };
     let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::END;
-    if self.current_token.kind != TokenKind::FUNCTION {
+    let (identifier, parameters, return_type) =
+      self.parse_block_header(Self::parse_function_header, (String::new(), vec![], None));
+    let statements: Vec<Statement> = self.parse_block_statements(block, to_stop);
+    if self.current_token.kind != TokenKind::END {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected End, but {}", self.current_token.kind),
+      ));
+    }
+    self.next_token();
+    // `End Function` and `End Sub` may close the procedure as well as `End`.
+    if self.current_token.kind == end_keyword {
+      self.next_token();
+    }
+
+    let f = FunctionDeclaration {
+      kind,
+      identifier,
+      parameters,
+      return_type,
+      statements,
+      span: self.span_from(&start),
+    };
+    debug!("<<< parse_function_declaration {:?}", f);
+    Ok(f)
+  }
+
+  fn parse_function_header(
+    &mut self,
+  ) -> Result<(String, Vec<Parameter>, Option<RuntimeType>), ParseError> {
+    let is_sub = self.current_token.kind == TokenKind::SUB;
+    if !matches!(
+      self.current_token.kind,
+      TokenKind::FUNCTION | TokenKind::SUB
+    ) {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected Fn or Function, but {}", self.current_token.kind),
+        format!(
+          "Expected Fn, Function or Sub, but {}",
+          self.current_token.kind
+        ),
       ));
     }
 
@@ -71,7 +161,8 @@ impl<'a> Parser<'a> {
         format!("Expected Ident, but {}", self.current_token.kind),
       ));
     }
-    let identifier = self.current_token.value.clone();
+    let identifier = self.current_identifier();
+    let suffix_type = self.current_identifier_type();
     self.next_token();
     if self.current_token.kind != TokenKind::LPAREN {
       return Err(self.raise_error(
@@ -80,20 +171,30 @@ impl<'a> Parser<'a> {
       ));
     }
     self.next_token();
-    let mut arguments: Vec<String> = vec![];
+    let mut parameters: Vec<Parameter> = vec![];
     loop {
       if self.current_token.kind == TokenKind::RPAREN {
         break;
       }
-      if self.current_token.kind != TokenKind::IDENT {
+      if let Some(last) = parameters.last() {
+        if last.param_array {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!("ParamArray {} must be the last parameter", last.name),
+          ));
+        }
+      }
+      let parameter = self.parse_parameter()?;
+      if !parameter.optional && !parameter.param_array && parameters.iter().any(|p| p.optional) {
         return Err(self.raise_error(
           ParseErrorType::InvalidToken,
-          format!("Expected Ident, but {}", self.current_token.kind),
+          format!(
+            "Parameter {} after Optional parameters must be Optional",
+            parameter.name
+          ),
         ));
       }
-      let argument = self.current_token.value.clone();
-      arguments.push(argument);
-      self.next_token();
+      parameters.push(parameter);
       if self.current_token.kind == TokenKind::RPAREN {
         continue;
       }
@@ -106,122 +207,297 @@ impl<'a> Parser<'a> {
       self.next_token();
     }
     self.next_token();
-    if self.current_token.kind != TokenKind::EOL {
+    if is_sub && self.current_token.kind == TokenKind::AS {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected EOL, but {}", self.current_token.kind),
+        format!("Sub {} cannot have a return type", identifier),
       ));
     }
-    self.next_token();
-    let statements: Vec<Statement> = self.parse_statements(to_stop)?;
-    if self.current_token.kind != TokenKind::END {
+    let return_type = self.parse_type_clause(suffix_type)?;
+    self.expect_end_of_statement()?;
+    Ok((identifier, parameters, return_type))
+  }
+
+  /*
+  - `Parameter`                 ::= "Optional"? ( "ByVal" | "ByRef" )? "IDENT" `TypeClause`? ( "=" `Expression` )? |
+                                    "ParamArray" "IDENT" ( "(" ")" )? `TypeClause`?
+  */
+  fn parse_parameter(&mut self) -> Result<Parameter, ParseError> {
+    let mut optional = false;
+    let mut param_array = false;
+    let mut passing = PassingMode::ByVal;
+    match self.current_token.kind {
+      TokenKind::OPTIONAL => {
+        optional = true;
+        self.next_token();
+      }
+      TokenKind::PARAMARRAY => {
+        param_array = true;
+        self.next_token();
+      }
+      _ => {}
+    }
+    if !param_array {
+      match self.current_token.kind {
+        TokenKind::BYVAL => self.next_token(),
+        TokenKind::BYREF => {
+          passing = PassingMode::ByRef;
+          self.next_token();
+        }
+        _ => {}
+      }
+    }
+    if self.current_token.kind != TokenKind::IDENT {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected End, but {}", self.current_token.kind),
+        format!("Expected Ident, but {}", self.current_token.kind),
       ));
     }
+    let name = self.current_identifier();
+    let suffix_type = self.current_identifier_type();
     self.next_token();
-
-    let f = FunctionDeclaration {
-      identifier,
-      arguments,
-      statements,
-    };
-    debug!("<<< parse_function_declaration {:?}", f);
-    Ok(f)
+    if param_array && self.current_token.kind == TokenKind::LPAREN {
+      self.next_token();
+      if self.current_token.kind != TokenKind::RPAREN {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Expected RPAREN, but {}", self.current_token.kind),
+        ));
+      }
+      self.next_token();
+    }
+    let type_name = self.parse_type_clause(suffix_type)?;
+    let mut default: Option<Expression> = None;
+    if optional && self.current_token.kind == TokenKind::ASSIGN {
+      self.next_token();
+      default = Some(self.parse_expression()?);
+    }
+    Ok(Parameter {
+      name,
+      passing,
+      type_name,
+      optional,
+      default,
+      param_array,
+    })
   }
 
-  fn parse_statements(
-    &mut self,
-    to_stop: fn(&TokenKind) -> bool,
-  ) -> Result<Vec<Statement>, ParseError> {
+  /// Parses statements up to a token in `to_stop`. Any other keyword which closes a block
+  /// also stops, so that a missing `Next` or `End If` is reported by the enclosing block
+  /// instead of swallowing the rest of the source. An erroneous statement is recorded and
+  /// skipped.
+  fn parse_statements(&mut self, to_stop: fn(&TokenKind) -> bool) -> Vec<Statement> {
     let mut statements: Vec<Statement> = vec![];
     loop {
       debug!("parse_statements:loop {:?}", self.current_token);
-      if to_stop(&self.current_token.kind) {
-        debug!(
-          "parse_statements: stopeed with: {}",
-          self.current_token.kind
-        );
+      let k = self.current_token.kind;
+      if to_stop(&k) || Self::is_block_end(&k) {
+        debug!("parse_statements: stopeed with: {}", k);
         break;
       }
-      let s = self.parse_statement()?;
-      statements.push(s);
+      let parsed = self.parse_statement().and_then(|s| {
+        let k = self.current_token.kind;
+        if self.is_end_of_statement() {
+          self.next_token();
+        } else if !(to_stop(&k) || Self::is_block_end(&k)) {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!("Expected an end keyword of statement, but {}", k),
+          ));
+        }
+        Ok(s)
+      });
+      match parsed {
+        Ok(s) => statements.push(s),
+        Err(e) => {
+          self.errors.push(e);
+          self.synchronize();
+        }
+      }
+    }
+    statements
+  }
+
+  /// Parses the body of a loop or a function, which `Exit` and `Continue` can refer to.
+  fn parse_block_statements(
+    &mut self,
+    block: BlockKind,
+    to_stop: fn(&TokenKind) -> bool,
+  ) -> Vec<Statement> {
+    self.blocks.push(block);
+    let statements = self.parse_statements(to_stop);
+    self.blocks.pop();
+    statements
+  }
+
+  fn is_block_end(kind: &TokenKind) -> bool {
+    matches!(
+      *kind,
+      TokenKind::EOF
+        | TokenKind::FUNCTION
+        | TokenKind::SUB
+        | TokenKind::END
+        | TokenKind::NEXT
+        | TokenKind::ELSE
+        | TokenKind::ELSEIF
+        | TokenKind::CASE
+        | TokenKind::LOOP
+        | TokenKind::WEND
+    )
+  }
+
+  /// Panic-mode recovery: skips to the end of the erroneous statement, or to the keyword
+  /// (`End`, `Next`, `Fn`, ...) which closes the enclosing block.
+  fn synchronize(&mut self) {
+    loop {
       let k = self.current_token.kind;
-      if !(k == TokenKind::EOL || to_stop(&k)) {
-        return Err(self.raise_error(
-          ParseErrorType::InvalidToken,
-          format!("Expected an end keyword of statement, but {}", k),
-        ));
+      if Self::is_block_end(&k) {
+        return;
       }
       self.next_token();
+      if k == TokenKind::EOL || k == TokenKind::COLON {
+        return;
+      }
+    }
+  }
+
+  fn skip_line(&mut self) {
+    loop {
+      let k = self.current_token.kind;
+      if k == TokenKind::EOF {
+        return;
+      }
+      self.next_token();
+      if k == TokenKind::EOL || k == TokenKind::COLON {
+        return;
+      }
+    }
+  }
+
+  /// Parses the first line of a block such as `If ... Then` or `For ... To ...`.
+  /// On error the rest of the line is skipped and `default` is used, so that the body of the
+  /// block and its closing keyword are still parsed.
+  fn parse_block_header<T>(
+    &mut self,
+    parse: fn(&mut Self) -> Result<T, ParseError>,
+    default: T,
+  ) -> T {
+    match parse(self) {
+      Ok(header) => header,
+      Err(e) => {
+        self.errors.push(e);
+        self.skip_line();
+        default
+      }
     }
-    return Ok(statements);
   }
 
   fn parse_statement(&mut self) -> Result<Statement, ParseError> {
     debug!(">>> parse_statement {}", self.current_token.kind);
-    let s = match self.current_token.kind {
+    let start = self.current_span();
+    let kind = match self.current_token.kind {
       TokenKind::IF => self.parse_if_statement()?,
       TokenKind::FOR => self.parse_for_statement()?,
+      TokenKind::SELECT => self.parse_select_statement()?,
+      TokenKind::DO => self.parse_do_statement()?,
+      TokenKind::WHILE => self.parse_while_statement()?,
       TokenKind::DIM => self.parse_const_assignment_statement()?,
       TokenKind::CONST => self.parse_const_assignment_statement()?,
-      TokenKind::EOL => Statement::Empty,
+      TokenKind::REDIM => self.parse_redim_statement()?,
+      TokenKind::EOL | TokenKind::COLON => StatementKind::Empty,
       TokenKind::RETURN => self.parse_return_statement()?,
+      TokenKind::EXIT | TokenKind::CONTINUE => self.parse_jump_statement()?,
       _ => self.parse_expression_statement()?,
     };
+    let span = match kind {
+      StatementKind::Empty => start,
+      _ => self.span_from(&start),
+    };
+    let s = Statement::new(kind, span);
     debug!("<<< parse_statement {}", s);
     Ok(s)
   }
 
-  fn parse_return_statement(&mut self) -> Result<Statement,ParseError> {
+  fn parse_return_statement(&mut self) -> Result<StatementKind, ParseError> {
     debug!(">>> parse_return_statement");
     self.next_token();
-    let expression = self.parse_expression()?;
-    if self.current_token.kind != TokenKind::EOL {
+    let k = self.current_token.kind;
+    if self.is_end_of_statement() || k == TokenKind::ELSE || Self::is_block_end(&k) {
+      return Ok(StatementKind::ReturnStatement { expression: None });
+    }
+    if self.blocks.contains(&BlockKind::Sub) {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected EOL, but {}", self.current_token.kind),
+        String::from("Sub cannot return a value"),
       ));
     }
+    let expression = self.parse_expression()?;
     debug!("<<< parse_return_statement {}", expression);
-    Ok(Statement::ReturnStatement { expression })
+    Ok(StatementKind::ReturnStatement {
+      expression: Some(expression),
+    })
+  }
+
+  /// Parses `Exit For`, `Exit Do`, `Exit Function`, `Exit Sub`, `Continue For` and
+  /// `Continue Do`.
+  fn parse_jump_statement(&mut self) -> Result<StatementKind, ParseError> {
+    let is_exit = self.current_token.kind == TokenKind::EXIT;
+    let keyword = if is_exit { "Exit" } else { "Continue" };
+    self.next_token();
+    let block = match self.current_token.kind {
+      TokenKind::FOR => BlockKind::For,
+      TokenKind::DO => BlockKind::Do,
+      TokenKind::FUNCTION if is_exit => BlockKind::Function,
+      TokenKind::SUB if is_exit => BlockKind::Sub,
+      k => {
+        let expected = if is_exit {
+          "For, Do, Function or Sub"
+        } else {
+          "For or Do"
+        };
+        let e = self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Expected {}, but {}", expected, k),
+        );
+        // Skip the keyword so that recovery does not take it for the end of a block.
+        if !self.is_end_of_statement() {
+          self.next_token();
+        }
+        return Err(e);
+      }
+    };
+    if !self.blocks.contains(&block) {
+      let e = self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("{} {} is not within {}", keyword, block, block),
+      );
+      // As above, `Sub` or `Function` here must not be taken for the start of a procedure.
+      self.next_token();
+      return Err(e);
+    }
+    self.next_token();
+    Ok(match is_exit {
+      true => StatementKind::ExitStatement(block),
+      false => StatementKind::ContinueStatement(block),
+    })
   }
 
-  fn parse_if_statement(&mut self) -> Result<Statement, ParseError> {
+  fn parse_if_statement(&mut self) -> Result<StatementKind, ParseError> {
     debug!(">>> parse_if_statement");
     let mut if_blocks: Vec<(Expression, Vec<Statement>)> = vec![];
     let mut else_statements: Vec<Statement> = vec![];
-    let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::ELSE || *k == TokenKind::END;
+    let mut has_else = false;
+    let to_stop: fn(&TokenKind) -> bool =
+      |k| matches!(*k, TokenKind::ELSE | TokenKind::ELSEIF | TokenKind::END);
 
     {
-      if self.current_token.kind != TokenKind::IF {
-        return Err(self.raise_error(
-          ParseErrorType::InvalidToken,
-          format!("Expected IF, but {}", self.current_token.kind),
-        ));
-      }
-      self.next_token();
-      
-      debug!(">>> condition");
-      let condition = self.parse_expression()?;
-      debug!("<<< condition");
       debug!(">>> then_block");
-      if self.current_token.kind != TokenKind::THEN {
-        return Err(self.raise_error(
-          ParseErrorType::InvalidToken,
-          format!("Expected THEN, but {}", self.current_token.kind),
-        ));
-      }
-      self.next_token();
-      if self.current_token.kind != TokenKind::EOL {
-        return Err(self.raise_error(
-          ParseErrorType::InvalidToken,
-          format!("Expected EOL, but {}", self.current_token.kind),
-        ));
+      let (condition, single_line) =
+        self.parse_block_header(Self::parse_if_header, (self.missing_expression(), false));
+      if single_line {
+        return self.parse_single_line_if(condition);
       }
-      self.next_token();
-      let statements: Vec<Statement> = self.parse_statements(to_stop)?;
+      let statements: Vec<Statement> = self.parse_statements(to_stop);
       debug!("<<< then_block");
       if_blocks.push((condition, statements));
     }
@@ -242,215 +518,550 @@ impl<'a> Parser<'a> {
         debug!("*** BREAK");
         break;
       }
-      if self.current_token.kind != TokenKind::ELSE {
+      if !matches!(self.current_token.kind, TokenKind::ELSE | TokenKind::ELSEIF) {
         return Err(self.raise_error(
           ParseErrorType::InvalidToken,
           format!("Expected ELSE, but {}", self.current_token.kind),
         ));
       }
-      if 0 < else_statements.len() {
+      if has_else {
         return Err(self.raise_error(
           ParseErrorType::InvalidToken,
           format!("Not expected ELSE, but {}", self.current_token.kind),
         ));
       }
-      self.next_token();
-      if self.current_token.kind == TokenKind::IF {
-        self.next_token();
-        let c = self.parse_expression()?;
-        if self.current_token.kind != TokenKind::THEN {
-          return Err(self.raise_error(
-            ParseErrorType::InvalidToken,
-            format!("Expected THEN, but {}", self.current_token.kind),
-          ));
-        }
-        self.next_token();
-        if self.current_token.kind != TokenKind::EOL {
-          return Err(self.raise_error(
-            ParseErrorType::InvalidToken,
-            format!("Expected EOL, but {}", self.current_token.kind),
-          ));
+      if self.current_token.kind == TokenKind::ELSEIF || self.next_token.kind == TokenKind::IF {
+        if self.current_token.kind == TokenKind::ELSE {
+          self.next_token();
         }
-        self.next_token();
-        let statements: Vec<Statement> = self.parse_statements(to_stop)?;
+        let c = self.parse_block_header(Self::parse_else_if_header, self.missing_expression());
+        let statements: Vec<Statement> = self.parse_statements(to_stop);
         if_blocks.push((c, statements));
       } else {
         debug!(">>> ELSE");
-        if self.current_token.kind != TokenKind::EOL {
-          return Err(self.raise_error(
-            ParseErrorType::InvalidToken,
-            format!("Expected EOL, but {}", self.current_token.kind),
-          ));
-        }
-        self.next_token();
+        has_else = true;
+        self.parse_block_header(Self::parse_else_header, ());
         debug!("<<< ELSE {}", self.current_token.kind);
-        else_statements = self.parse_statements(to_stop)?;
+        else_statements = self.parse_statements(to_stop);
         debug!(">>>");
       }
     }
 
-    Ok(Statement::IfStatement {
+    Ok(StatementKind::IfStatement {
       if_blocks,
       else_statements,
     })
   }
 
-  fn parse_for_statement(&mut self) -> Result<Statement, ParseError> {
-    debug!(">>> parse_for_statement {}", self.current_token.kind);
-
-    let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::NEXT;
-    if self.current_token.kind != TokenKind::FOR {
-      return Err(self.raise_error(
-        ParseErrorType::InvalidToken,
-        format!("Expected For, but {}", self.current_token.kind),
-      ));
-    }
-
-    self.next_token();
-    if self.current_token.kind != TokenKind::IDENT {
+  /// Parses `If Expression Then` or `ElseIf Expression Then`. The flag is true when a
+  /// statement follows `Then` on the same line, i.e. the single-line form of `If`.
+  fn parse_if_header(&mut self) -> Result<(Expression, bool), ParseError> {
+    if !matches!(self.current_token.kind, TokenKind::IF | TokenKind::ELSEIF) {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected Ident, but {}", self.current_token.kind),
+        format!("Expected IF, but {}", self.current_token.kind),
       ));
     }
-    let loop_counter = self.current_token.value.clone();
     self.next_token();
-    debug!("*** loop_counter {},{:?}", loop_counter, self.current_token);
-    if self.current_token.kind != TokenKind::ASSIGN {
+    debug!(">>> condition");
+    let condition = self.parse_expression()?;
+    debug!("<<< condition");
+    if self.current_token.kind != TokenKind::THEN {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected '=', but {}", self.current_token.kind),
+        format!("Expected THEN, but {}", self.current_token.kind),
       ));
     }
     self.next_token();
-    let loop_counter_from = self.parse_expression()?;
-    if self.current_token.kind != TokenKind::TO {
-      return Err(self.raise_error(
-        ParseErrorType::InvalidToken,
-        format!("Expected To, but {}", self.current_token.kind),
-      ));
+    if !self.is_end_of_statement() {
+      return Ok((condition, true));
     }
     self.next_token();
-    let loop_counter_to = self.parse_expression()?;
-    if self.current_token.kind != TokenKind::EOL {
+    Ok((condition, false))
+  }
+
+  fn parse_else_if_header(&mut self) -> Result<Expression, ParseError> {
+    let (condition, single_line) = self.parse_if_header()?;
+    if single_line {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
         format!("Expected EOL, but {}", self.current_token.kind),
       ));
     }
+    Ok(condition)
+  }
+
+  fn parse_else_header(&mut self) -> Result<(), ParseError> {
     self.next_token();
-    let block: Vec<Statement> = self.parse_statements(to_stop)?;
-    if self.current_token.kind != TokenKind::NEXT {
-      return Err(self.raise_error(
-        ParseErrorType::InvalidToken,
-        format!("Expected Next, but {}", self.current_token.kind),
-      ));
+    self.expect_end_of_statement()
+  }
+
+  /// Parses the rest of `If c Then s1: s2 Else s3: s4` after `Then`. The `Then` part may be
+  /// empty as in `If c Then Else s`. The line break which ends the statement is left to the
+  /// caller.
+  fn parse_single_line_if(&mut self, condition: Expression) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_single_line_if");
+    let then_statements = match self.current_token.kind {
+      TokenKind::ELSE => vec![],
+      _ => self.parse_single_line_statements()?,
+    };
+    let mut else_statements: Vec<Statement> = vec![];
+    if self.current_token.kind == TokenKind::ELSE {
+      self.next_token();
+      else_statements = self.parse_single_line_statements()?;
     }
-    self.next_token();
-    Ok(Statement::ForStatement {
-      loop_counter,
-      loop_counter_from,
-      loop_counter_to,
-      block,
+    Ok(StatementKind::IfStatement {
+      if_blocks: vec![(condition, then_statements)],
+      else_statements,
     })
   }
 
-  /*
-  - `ExpressionStatement`       ::= `Assignment` |
-                                    `MethodInvocation`
-  */
-  fn parse_expression_statement(&mut self) -> Result<Statement, ParseError> {
-    debug!(">>> parse_expression_statement {}", self.current_token.kind);
-    match self.parse_assignment()? {
-      Some((identifier, expression)) => Ok(Statement::Assignment {
-        identifier,
-        expression,
-      }),
-      None => self.parse_method_invocation(),
+  /// Parses statements separated by `:` up to `Else` or the end of the line.
+  fn parse_single_line_statements(&mut self) -> Result<Vec<Statement>, ParseError> {
+    let mut statements: Vec<Statement> = vec![];
+    loop {
+      statements.push(self.parse_statement()?);
+      match self.current_token.kind {
+        TokenKind::COLON => self.next_token(),
+        TokenKind::ELSE | TokenKind::EOL | TokenKind::EOF => return Ok(statements),
+        k => {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!("Expected an end keyword of statement, but {}", k),
+          ))
+        }
+      }
     }
   }
 
-  fn parse_const_assignment_statement(&mut self) -> Result<Statement, ParseError> {
-    debug!(">>> parse_const_assignment_statement");
+  fn parse_select_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_select_statement");
+    let to_stop: fn(&TokenKind) -> bool = |k| matches!(*k, TokenKind::CASE | TokenKind::END);
+    let expression = self.parse_block_header(Self::parse_select_header, self.missing_expression());
+    while self.is_end_of_statement() {
+      self.next_token();
+    }
+    let mut case_blocks: Vec<(Vec<CaseCondition>, Vec<Statement>)> = vec![];
+    let mut else_statements: Option<Vec<Statement>> = None;
+    loop {
+      if self.current_token.kind == TokenKind::END {
+        self.next_token();
+        if self.current_token.kind != TokenKind::SELECT {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!("Expected SELECT, but {}", self.current_token.kind),
+          ));
+        }
+        self.next_token();
+        break;
+      }
+      if self.current_token.kind != TokenKind::CASE {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Expected CASE, but {}", self.current_token.kind),
+        ));
+      }
+      if else_statements.is_some() {
+        // Keep parsing the clause so that only this error is reported.
+        let e = self.raise_error(
+          ParseErrorType::InvalidToken,
+          String::from("Not expected CASE after Case Else"),
+        );
+        self.errors.push(e);
+      }
+      match self.parse_block_header(Self::parse_case_header, Some(vec![])) {
+        Some(conditions) => {
+          let statements = self.parse_statements(to_stop);
+          case_blocks.push((conditions, statements));
+        }
+        None => else_statements = Some(self.parse_statements(to_stop)),
+      }
+    }
+    debug!("<<< parse_select_statement");
+    Ok(StatementKind::SelectStatement {
+      expression,
+      case_blocks,
+      else_statements: else_statements.unwrap_or_default(),
+    })
+  }
+
+  /// Parses `Select Case Expression EOL`.
+  fn parse_select_header(&mut self) -> Result<Expression, ParseError> {
     self.next_token();
-    match self.parse_assignment()? {
-      Some((identifier, expression)) => Ok(Statement::Declaration {
-        identifier,
-        expression,
-      }),
-      None => Err(self.raise_error(
+    if self.current_token.kind != TokenKind::CASE {
+      return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected Const or Dim, but {}", self.current_token.kind),
-      )),
+        format!("Expected CASE, but {}", self.current_token.kind),
+      ));
     }
+    self.next_token();
+    let expression = self.parse_expression()?;
+    self.expect_end_of_statement()?;
+    Ok(expression)
   }
 
-  fn parse_assignment(&mut self) -> Result<Option<(String, Expression)>, ParseError> {
-    if self.current_token.kind != TokenKind::IDENT {
+  /// Parses `Case CaseCondition, ... EOL`, or `Case Else EOL` which returns None.
+  fn parse_case_header(&mut self) -> Result<Option<Vec<CaseCondition>>, ParseError> {
+    self.next_token();
+    if self.current_token.kind == TokenKind::ELSE {
+      self.next_token();
+      self.expect_end_of_statement()?;
       return Ok(None);
     }
-    let identifier = self.current_token.value.clone();
-    if self.next_token.kind != TokenKind::ASSIGN {
-      return Ok(None);
+    let mut conditions: Vec<CaseCondition> = vec![];
+    loop {
+      conditions.push(self.parse_case_condition()?);
+      if self.current_token.kind != TokenKind::COMMA {
+        break;
+      }
+      self.next_token();
+    }
+    self.expect_end_of_statement()?;
+    Ok(Some(conditions))
+  }
+
+  fn parse_case_condition(&mut self) -> Result<CaseCondition, ParseError> {
+    if self.current_token.kind == TokenKind::IS {
+      self.next_token();
+      let op = match Self::comparison_operator(&self.current_token.kind) {
+        Some(op) => op,
+        None => {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!(
+              "Expected a comparison operator, but {}",
+              self.current_token.kind
+            ),
+          ))
+        }
+      };
+      self.next_token();
+      return Ok(CaseCondition::Comparison(op, self.parse_expression()?));
+    }
+    let e = self.parse_expression()?;
+    if self.current_token.kind != TokenKind::TO {
+      return Ok(CaseCondition::Value(e));
     }
     self.next_token();
+    Ok(CaseCondition::Range(e, self.parse_expression()?))
+  }
+
+  fn parse_do_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_do_statement");
+    let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::LOOP;
+    let pre_condition = self.parse_block_header(Self::parse_do_header, None);
+    let block: Vec<Statement> = self.parse_block_statements(BlockKind::Do, to_stop);
+    if self.current_token.kind != TokenKind::LOOP {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected Loop, but {}", self.current_token.kind),
+      ));
+    }
     self.next_token();
-    let expression = self.parse_expression()?;
+    let post_condition = self.parse_loop_condition()?;
+    if pre_condition.is_some() && post_condition.is_some() {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        String::from("Loop condition is already given after Do"),
+      ));
+    }
+    Ok(StatementKind::DoStatement {
+      pre_condition,
+      block,
+      post_condition,
+    })
+  }
 
-    return Ok(Some((identifier, expression)));
+  /// Parses `Do [While|Until Expression] EOL`.
+  fn parse_do_header(&mut self) -> Result<Option<LoopCondition>, ParseError> {
+    self.next_token();
+    let condition = self.parse_loop_condition()?;
+    self.expect_end_of_statement()?;
+    Ok(condition)
   }
 
-  fn parse_function_invocation(&mut self) -> Result<Expression, ParseError> {
-    debug!(">>> parse_function_invocation");
+  fn parse_loop_condition(&mut self) -> Result<Option<LoopCondition>, ParseError> {
+    match self.current_token.kind {
+      TokenKind::WHILE => {
+        self.next_token();
+        Ok(Some(LoopCondition::While(self.parse_expression()?)))
+      }
+      TokenKind::UNTIL => {
+        self.next_token();
+        Ok(Some(LoopCondition::Until(self.parse_expression()?)))
+      }
+      _ => Ok(None),
+    }
+  }
+
+  fn parse_while_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_while_statement");
+    let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::WEND;
+    let condition = self.parse_block_header(Self::parse_while_header, self.missing_expression());
+    let block: Vec<Statement> = self.parse_block_statements(BlockKind::Do, to_stop);
+    if self.current_token.kind != TokenKind::WEND {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected Wend, but {}", self.current_token.kind),
+      ));
+    }
+    self.next_token();
+    Ok(StatementKind::DoStatement {
+      pre_condition: Some(LoopCondition::While(condition)),
+      block,
+      post_condition: None,
+    })
+  }
+
+  /// Parses `While Expression EOL`.
+  fn parse_while_header(&mut self) -> Result<Expression, ParseError> {
+    self.next_token();
+    let condition = self.parse_expression()?;
+    self.expect_end_of_statement()?;
+    Ok(condition)
+  }
+
+  fn parse_for_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_for_statement {}", self.current_token.kind);
+    if self.next_token.kind == TokenKind::EACH {
+      return self.parse_for_each_statement();
+    }
+
+    let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::NEXT;
+    let (loop_counter, loop_counter_from, loop_counter_to, loop_counter_step) = self
+      .parse_block_header(
+        Self::parse_for_header,
+        (
+          String::new(),
+          self.missing_expression(),
+          self.missing_expression(),
+          None,
+        ),
+      );
+    let block: Vec<Statement> = self.parse_block_statements(BlockKind::For, to_stop);
+    self.parse_next(&loop_counter)?;
+    Ok(StatementKind::ForStatement {
+      loop_counter,
+      loop_counter_from,
+      loop_counter_to,
+      loop_counter_step,
+      block,
+    })
+  }
+
+  fn parse_for_header(
+    &mut self,
+  ) -> Result<(String, Expression, Expression, Option<Expression>), ParseError> {
+    if self.current_token.kind != TokenKind::FOR {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected For, but {}", self.current_token.kind),
+      ));
+    }
+
+    self.next_token();
     if self.current_token.kind != TokenKind::IDENT {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected IDENT, but {}", self.current_token.kind),
+        format!("Expected Ident, but {}", self.current_token.kind),
       ));
     }
-    let identifier = self.current_token.value.clone();
+    let loop_counter = self.current_identifier();
     self.next_token();
-    if self.current_token.kind != TokenKind::LPAREN {
+    debug!("*** loop_counter {},{:?}", loop_counter, self.current_token);
+    if self.current_token.kind != TokenKind::ASSIGN {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected LPAREN, but {}", self.current_token.kind),
+        format!("Expected '=', but {}", self.current_token.kind),
       ));
     }
     self.next_token();
-    let mut arguments: Vec<Expression> = vec![];
-    if self.current_token.kind != TokenKind::RPAREN {
-      loop {
-        arguments.push(self.parse_expression()?);
-        if self.current_token.kind == TokenKind::RPAREN {
-          break;
-        }
-        if self.current_token.kind != TokenKind::COMMA {
-          return Err(self.raise_error(
-            ParseErrorType::InvalidToken,
-            format!("Expected COMMA, but {}", self.current_token.kind),
-          ));
-        }
-        self.next_token();
+    let loop_counter_from = self.parse_expression()?;
+    if self.current_token.kind != TokenKind::TO {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected To, but {}", self.current_token.kind),
+      ));
+    }
+    self.next_token();
+    let loop_counter_to = self.parse_expression()?;
+    let mut loop_counter_step: Option<Expression> = None;
+    if self.current_token.kind == TokenKind::STEP {
+      self.next_token();
+      loop_counter_step = Some(self.parse_expression()?);
+    }
+    self.expect_end_of_statement()?;
+    Ok((
+      loop_counter,
+      loop_counter_from,
+      loop_counter_to,
+      loop_counter_step,
+    ))
+  }
+
+  fn parse_for_each_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_for_each_statement");
+    let to_stop: fn(&TokenKind) -> bool = |k| *k == TokenKind::NEXT;
+    let (element, collection) = self.parse_block_header(
+      Self::parse_for_each_header,
+      (String::new(), self.missing_expression()),
+    );
+    let block: Vec<Statement> = self.parse_block_statements(BlockKind::For, to_stop);
+    self.parse_next(&element)?;
+    Ok(StatementKind::ForEachStatement {
+      element,
+      collection,
+      block,
+    })
+  }
+
+  /// Parses `For Each Ident In Expression EOL`.
+  fn parse_for_each_header(&mut self) -> Result<(String, Expression), ParseError> {
+    self.next_token();
+    self.next_token();
+    if self.current_token.kind != TokenKind::IDENT {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected Ident, but {}", self.current_token.kind),
+      ));
+    }
+    let element = self.current_identifier();
+    self.next_token();
+    if self.current_token.kind != TokenKind::IN {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected In, but {}", self.current_token.kind),
+      ));
+    }
+    self.next_token();
+    let collection = self.parse_expression()?;
+    self.expect_end_of_statement()?;
+    Ok((element, collection))
+  }
+
+  /// Parses `Next` with the optional name of the loop variable, which must match the `For`.
+  fn parse_next(&mut self, loop_variable: &str) -> Result<(), ParseError> {
+    if self.current_token.kind != TokenKind::NEXT {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected Next, but {}", self.current_token.kind),
+      ));
+    }
+    self.next_token();
+    if self.current_token.kind == TokenKind::IDENT {
+      let identifier = self.current_identifier();
+      if identifier.to_lowercase() != loop_variable.to_lowercase() {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Expected Next {}, but Next {}", loop_variable, identifier),
+        ));
       }
+      self.next_token();
     }
-    let e = Expression::FunctionInvocation {
-      identifier,
-      arguments,
+    Ok(())
+  }
+
+  /*
+  - `ExpressionStatement`       ::= `Assignment` |
+                                    `MethodInvocation`
+  */
+  fn parse_expression_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_expression_statement {}", self.current_token.kind);
+    match self.parse_assignment()? {
+      Some((identifier, expression)) => Ok(StatementKind::Assignment {
+        identifier,
+        expression,
+      }),
+      None => self.parse_method_invocation(),
+    }
+  }
+
+  /*
+  - `Declaration`               ::= ( "Const" | "Dim" ) "IDENT" `TypeClause`? "=" `Expression` |
+                                    "Dim" "IDENT" `ArrayBounds`? `TypeClause`?
+  */
+  fn parse_const_assignment_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_const_assignment_statement");
+    let is_const = self.current_token.kind == TokenKind::CONST;
+    self.next_token();
+    if self.current_token.kind != TokenKind::IDENT {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected Ident, but {}", self.current_token.kind),
+      ));
+    }
+    let identifier = self.current_identifier();
+    let suffix_type = self.current_identifier_type();
+    self.next_token();
+    let dimensions = match self.current_token.kind {
+      TokenKind::LPAREN if !is_const => Some(self.parse_array_bounds()?),
+      _ => None,
     };
-    debug!("<<< parse_function_invocation: {}", e);
-    return Ok(e);
+    let type_name = self.parse_type_clause(suffix_type)?;
+    let expression = match self.current_token.kind {
+      TokenKind::ASSIGN if dimensions.is_some() => {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Array {} cannot have an initializer", identifier),
+        ))
+      }
+      TokenKind::ASSIGN => {
+        self.next_token();
+        Some(self.parse_expression()?)
+      }
+      _ if is_const => {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Expected ASSIGN, but {}", self.current_token.kind),
+        ))
+      }
+      _ => None,
+    };
+    Ok(StatementKind::Declaration {
+      identifier,
+      type_name,
+      dimensions,
+      expression,
+    })
   }
 
-  fn parse_method_invocation(&mut self) -> Result<Statement, ParseError> {
-    debug!(">>> parse_method_invocation");
+  /*
+  - `ReDimStatement`            ::= "ReDim" "Preserve"? "IDENT" `ArrayBounds`
+  */
+  fn parse_redim_statement(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_redim_statement");
+    self.next_token();
+    let preserve = self.current_token.kind == TokenKind::PRESERVE;
+    if preserve {
+      self.next_token();
+    }
     if self.current_token.kind != TokenKind::IDENT {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
-        format!("Expected IDENT, but {}", self.current_token.kind),
+        format!("Expected Ident, but {}", self.current_token.kind),
       ));
     }
-    let identifier = self.current_token.value.clone();
+    let identifier = self.current_identifier();
     self.next_token();
+    let dimensions = self.parse_array_bounds()?;
+    if dimensions.is_empty() {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("ReDim {} needs the bounds of the array", identifier),
+      ));
+    }
+    Ok(StatementKind::ReDimStatement {
+      identifier,
+      preserve,
+      dimensions,
+    })
+  }
+
+  /*
+  - `ArrayBounds`               ::= "(" ( `ArrayBound` ( "," `ArrayBound` )* )? ")"
+  - `ArrayBound`                ::= ( `Expression` "To" )? `Expression`
+  */
+  fn parse_array_bounds(&mut self) -> Result<Vec<ArrayBound>, ParseError> {
     if self.current_token.kind != TokenKind::LPAREN {
       return Err(self.raise_error(
         ParseErrorType::InvalidToken,
@@ -458,13 +1069,9 @@ impl<'a> Parser<'a> {
       ));
     }
     self.next_token();
-    let mut arguments: Vec<Expression> = vec![];
-    if self.current_token.kind != TokenKind::RPAREN {
-      loop {
-        arguments.push(self.parse_expression()?);
-        if self.current_token.kind == TokenKind::RPAREN {
-          break;
-        }
+    let mut bounds: Vec<ArrayBound> = vec![];
+    while self.current_token.kind != TokenKind::RPAREN {
+      if !bounds.is_empty() {
         if self.current_token.kind != TokenKind::COMMA {
           return Err(self.raise_error(
             ParseErrorType::InvalidToken,
@@ -473,173 +1080,287 @@ impl<'a> Parser<'a> {
         }
         self.next_token();
       }
+      let upper = self.parse_expression()?;
+      let bound = if self.current_token.kind == TokenKind::TO {
+        self.next_token();
+        ArrayBound {
+          lower: Some(upper),
+          upper: self.parse_expression()?,
+        }
+      } else {
+        ArrayBound { lower: None, upper }
+      };
+      bounds.push(bound);
     }
     self.next_token();
-    return Ok(Statement::MethodInvocation {
-      identifier,
-      arguments,
-    });
+    Ok(bounds)
   }
 
   /*
-  - `Expression`                ::= `LogicalXorExpression`
-  - `LogicalXorExpression`      ::= `LogicalOrExpression` |
-                                    `LogicalXorExpression` "Xor" `LogicalOrExpression`
+  - `TypeClause`                ::= "As" "IDENT"
   */
-  fn parse_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(">>> parse_expression {}", self.current_token.kind);
-    let e = {
-      let e = self.parse_logical_or_expression()?;
-      if self.current_token.kind != TokenKind::XOR {
-        e
-      } else {
-        self.next_token();
-        let right = self.parse_expression()?;
-        self.binary_operation(&e, BinaryOperator::XOR, &right)
-      }
-    };
-    debug!("<<< parse_expression {}", e);
-    Ok(e)
-  }
-
-  fn parse_logical_or_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(
-      ">>> parse_logical_or_expression {}",
-      self.current_token.kind
-    );
-    let e = {
-      let e = self.parse_logical_and_expression()?;
-      if self.current_token.kind != TokenKind::OR {
-        e
-      } else {
-        self.next_token();
-        let right = self.parse_logical_or_expression()?;
-        self.binary_operation(&e, BinaryOperator::OR, &right)
+  /// Parses `As Integer` and the like. Without it, the type is given by the suffix of the
+  /// identifier, e.g. `name$` is a String, or the variable is a Variant (None).
+  fn parse_type_clause(
+    &mut self,
+    suffix_type: Option<RuntimeType>,
+  ) -> Result<Option<RuntimeType>, ParseError> {
+    if self.current_token.kind != TokenKind::AS {
+      return Ok(suffix_type);
+    }
+    if suffix_type.is_some() {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        String::from("Type character cannot be used with As"),
+      ));
+    }
+    self.next_token();
+    if self.current_token.kind != TokenKind::IDENT {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected type name, but {}", self.current_token.kind),
+      ));
+    }
+    let name = self.current_token.value.clone();
+    let type_name = match name.to_lowercase().as_str() {
+      "integer" | "long" => Some(RuntimeType::Integer),
+      "biginteger" => Some(RuntimeType::BigInteger),
+      "double" | "single" => Some(RuntimeType::Double),
+      "currency" | "decimal" => Some(RuntimeType::Currency),
+      "string" => Some(RuntimeType::String),
+      "boolean" => Some(RuntimeType::Boolean),
+      "date" => Some(RuntimeType::Date),
+      "variant" | "object" => None,
+      _ => {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Unknown type {}", name),
+        ))
       }
     };
-    debug!("<<< parse_logical_or_expression {}", e);
-    Ok(e)
+    self.next_token();
+    Ok(type_name)
   }
 
-  fn parse_logical_and_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(
-      ">>> parse_logical_and_expression {}",
-      self.current_token.kind
-    );
-    let e = self.parse_logical_not_expression()?;
-    if self.current_token.kind != TokenKind::AND {
-      return Ok(e);
+  fn parse_assignment(&mut self) -> Result<Option<(String, Expression)>, ParseError> {
+    if self.current_token.kind != TokenKind::IDENT {
+      return Ok(None);
     }
+    let identifier = self.current_identifier();
+    if self.next_token.kind != TokenKind::ASSIGN {
+      return Ok(None);
+    }
+    self.next_token();
     self.next_token();
-    let right = self.parse_logical_and_expression()?;
-    Ok(self.binary_operation(&e, BinaryOperator::AND, &right))
+    let expression = self.parse_expression()?;
+
+    Ok(Some((identifier, expression)))
   }
 
-  fn parse_logical_not_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(
-      ">>> parse_logical_not_expression {}",
-      self.current_token.kind
-    );
-    if self.current_token.kind != TokenKind::NOT {
-      return self.parse_equality_expression();
+  fn parse_function_invocation(&mut self) -> Result<ExpressionKind, ParseError> {
+    debug!(">>> parse_function_invocation");
+    if self.current_token.kind != TokenKind::IDENT {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected IDENT, but {}", self.current_token.kind),
+      ));
     }
+    let identifier = self.current_identifier();
     self.next_token();
-    let e = self.parse_equality_expression()?;
-    Ok(self.unary_operation(UnaryOperator::NOT, &e))
+    let arguments = self.parse_arguments()?;
+    let e = ExpressionKind::FunctionInvocation {
+      identifier,
+      arguments,
+    };
+    debug!("<<< parse_function_invocation: {}", e);
+    Ok(e)
   }
 
-  fn parse_equality_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(">>> parse_equality_expression {}", self.current_token.kind);
-    let e = self.parse_additive_expression()?;
-    let op;
-    match self.current_token.kind {
-      TokenKind::ASSIGN => op = BinaryOperator::EQ,
-      TokenKind::NE => op = BinaryOperator::NE,
-      TokenKind::LT => op = BinaryOperator::LT,
-      TokenKind::GT => op = BinaryOperator::GT,
-      TokenKind::LE => op = BinaryOperator::LE,
-      TokenKind::GE => op = BinaryOperator::GE,
-      _ => return Ok(e),
+  /// Parses a call statement, or `a(i) = v` which starts as a call until `=` is found.
+  fn parse_method_invocation(&mut self) -> Result<StatementKind, ParseError> {
+    debug!(">>> parse_method_invocation");
+    match self.parse_function_invocation()? {
+      ExpressionKind::FunctionInvocation {
+        identifier,
+        arguments,
+      } => {
+        self.next_token();
+        if self.current_token.kind != TokenKind::ASSIGN {
+          return Ok(StatementKind::MethodInvocation {
+            identifier,
+            arguments,
+          });
+        }
+        if let Some((name, _)) = arguments.named.first() {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!("Index of {} cannot be named {}", identifier, name),
+          ));
+        }
+        self.next_token();
+        Ok(StatementKind::IndexedAssignment {
+          identifier,
+          indices: arguments.positional,
+          expression: self.parse_expression()?,
+        })
+      }
+      _ => unreachable!(),
     }
-    self.next_token();
-    let right = self.parse_equality_expression()?;
-    Ok(self.binary_operation(&e, op, &right))
   }
 
-  fn parse_additive_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(">>> parse_additive_expression {}", self.current_token.kind);
-    let e = self.parse_multiplicative_expression()?;
-    let op;
-    match self.current_token.kind {
-      TokenKind::PLUS => op = BinaryOperator::ADD,
-      TokenKind::MINUS => op = BinaryOperator::SUB,
-      _ => return Ok(e),
+  /*
+  - `Arguments`                 ::= "(" ( `Argument` ( "," `Argument` )* )? ")"
+  - `Argument`                  ::= `Expression` | "IDENT" ":=" `Expression`
+  */
+  /// Parses the arguments from `(` up to `)`, leaving `)` as the current token.
+  fn parse_arguments(&mut self) -> Result<Arguments, ParseError> {
+    if self.current_token.kind != TokenKind::LPAREN {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected LPAREN, but {}", self.current_token.kind),
+      ));
     }
     self.next_token();
-    let right = self.parse_additive_expression()?;
-    Ok(self.binary_operation(&e, op, &right))
+    let mut arguments = Arguments::default();
+    if self.current_token.kind == TokenKind::RPAREN {
+      return Ok(arguments);
+    }
+    loop {
+      if self.current_token.kind == TokenKind::IDENT && self.next_token.kind == TokenKind::COLONEQ {
+        let name = self.current_identifier();
+        self.next_token();
+        self.next_token();
+        arguments.named.push((name, self.parse_expression()?));
+      } else if arguments.named.is_empty() {
+        arguments.positional.push(self.parse_expression()?);
+      } else {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          String::from("Positional argument cannot follow named arguments"),
+        ));
+      }
+      if self.current_token.kind == TokenKind::RPAREN {
+        return Ok(arguments);
+      }
+      if self.current_token.kind != TokenKind::COMMA {
+        return Err(self.raise_error(
+          ParseErrorType::InvalidToken,
+          format!("Expected COMMA, but {}", self.current_token.kind),
+        ));
+      }
+      self.next_token();
+    }
   }
 
-  fn parse_multiplicative_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(
-      ">>> parse_multiplicative_expression {}",
-      self.current_token.kind
-    );
-    let e = self.parse_unary_expression()?;
-    let op;
-    match self.current_token.kind {
-      TokenKind::ASTERISK => op = BinaryOperator::MUL,
-      TokenKind::SLASH => op = BinaryOperator::DIV,
-      TokenKind::PERCENT => op = BinaryOperator::MOD,
-      TokenKind::MOD => op = BinaryOperator::MOD,
-      _ => return Ok(e),
+  /*
+  - `Expression`                ::= `UnaryExpression` ( `BinaryOperator` `UnaryExpression` )*
+  - `UnaryExpression`           ::= ( "Not" | "+" | "-" ) `Expression` | `Primary`
+  */
+  fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+    debug!(">>> parse_expression {}", self.current_token.kind);
+    let e = self.parse_binary_expression(Precedence::Lowest)?;
+    debug!("<<< parse_expression {}", e);
+    Ok(e)
+  }
+
+  /// Parses operators which bind tighter than `precedence`. The right operand is parsed with
+  /// the precedence of its operator, so operators of the same precedence are left associative,
+  /// e.g. `8 - 4 - 2` is `(8 - 4) - 2` and `2 ^ 3 ^ 2` is `(2 ^ 3) ^ 2` as in VB.
+  fn parse_binary_expression(&mut self, precedence: Precedence) -> Result<Expression, ParseError> {
+    let mut left = self.parse_unary_expression()?;
+    while let Some((p, op)) = Self::binary_operator(&self.current_token.kind) {
+      if p <= precedence {
+        break;
+      }
+      self.next_token();
+      let right = self.parse_binary_expression(p)?;
+      left = self.binary_operation(&left, op, &right);
     }
-    self.next_token();
-    let right = self.parse_unary_expression()?;
-    Ok(self.binary_operation(&e, op, &right))
+    Ok(left)
   }
 
+  /// Parses a prefix operator, whose operand takes the operators binding tighter than it,
+  /// e.g. `-2 ^ 2` is `-(2 ^ 2)` and `Not a = b` is `Not (a = b)`.
   fn parse_unary_expression(&mut self) -> Result<Expression, ParseError> {
     debug!(">>> parse_unary_expression {}", self.current_token.kind);
-    let op;
-    match self.current_token.kind {
-      TokenKind::PLUS => op = UnaryOperator::POSITIVE,
-      TokenKind::MINUS => op = UnaryOperator::NEGATIVE,
-      _ => {
-        return Ok(self.parse_exponential_expression()?);
-      }
-    }
+    let (precedence, op) = match Self::unary_operator(&self.current_token.kind) {
+      Some(o) => o,
+      None => return self.parse_primary(),
+    };
+    let start = self.current_span();
     self.next_token();
-    let e = self.parse_exponential_expression()?;
-    Ok(self.unary_operation(op, &e))
+    let e = self.parse_binary_expression(precedence)?;
+    Ok(self.unary_operation(&start, op, &e))
   }
 
-  fn parse_exponential_expression(&mut self) -> Result<Expression, ParseError> {
-    debug!(
-      ">>> parse_exponential_expression {}",
-      self.current_token.kind
-    );
-    let e = self.parse_primary()?;
-    if self.next_token.kind != TokenKind::HAT {
-      return Ok(e);
+  /// The table of binary operators with their precedence.
+  fn binary_operator(kind: &TokenKind) -> Option<(Precedence, BinaryOperator)> {
+    let operator = match kind {
+      TokenKind::XOR => (Precedence::Xor, BinaryOperator::XOR),
+      TokenKind::OR => (Precedence::Or, BinaryOperator::OR),
+      TokenKind::ORELSE => (Precedence::Or, BinaryOperator::ORELSE),
+      TokenKind::AND => (Precedence::And, BinaryOperator::AND),
+      TokenKind::ANDALSO => (Precedence::And, BinaryOperator::ANDALSO),
+      TokenKind::PLUS => (Precedence::Additive, BinaryOperator::ADD),
+      TokenKind::MINUS => (Precedence::Additive, BinaryOperator::SUB),
+      TokenKind::AMPERSAND => (Precedence::Concatenation, BinaryOperator::CONCAT),
+      TokenKind::MOD | TokenKind::PERCENT => (Precedence::Modulus, BinaryOperator::MOD),
+      TokenKind::BACKSLASH => (Precedence::IntegerDivision, BinaryOperator::INTDIV),
+      TokenKind::ASTERISK => (Precedence::Multiplicative, BinaryOperator::MUL),
+      TokenKind::SLASH => (Precedence::Multiplicative, BinaryOperator::DIV),
+      TokenKind::HAT => (Precedence::Exponential, BinaryOperator::EXPOTENTIAL),
+      k => (Precedence::Comparison, Self::comparison_operator(k)?),
+    };
+    Some(operator)
+  }
+
+  /// The table of prefix operators with their precedence.
+  fn unary_operator(kind: &TokenKind) -> Option<(Precedence, UnaryOperator)> {
+    match kind {
+      TokenKind::NOT => Some((Precedence::Not, UnaryOperator::NOT)),
+      TokenKind::PLUS => Some((Precedence::Unary, UnaryOperator::POSITIVE)),
+      TokenKind::MINUS => Some((Precedence::Unary, UnaryOperator::NEGATIVE)),
+      _ => None,
+    }
+  }
+
+  fn comparison_operator(kind: &TokenKind) -> Option<BinaryOperator> {
+    match kind {
+      TokenKind::ASSIGN => Some(BinaryOperator::EQ),
+      TokenKind::NE => Some(BinaryOperator::NE),
+      TokenKind::LT => Some(BinaryOperator::LT),
+      TokenKind::GT => Some(BinaryOperator::GT),
+      TokenKind::LE => Some(BinaryOperator::LE),
+      TokenKind::GE => Some(BinaryOperator::GE),
+      _ => None,
     }
-    self.next_token();
-    let right = self.parse_exponential_expression()?;
-    Ok(self.binary_operation(&e, BinaryOperator::EXPOTENTIAL, &right))
   }
 
   fn parse_primary(&mut self) -> Result<Expression, ParseError> {
     debug!(">>> parse_primary {}", self.current_token.kind);
-    let e = match self.current_token.kind {
+    let start = self.current_span();
+    let kind = match self.current_token.kind {
       TokenKind::IDENT => match self.next_token.kind {
         TokenKind::LPAREN => self.parse_function_invocation()?,
-        _ => Expression::Identifier(self.current_token.value.clone()),
+        _ => ExpressionKind::Identifier(self.current_identifier()),
       },
-      TokenKind::INT => Expression::Integer(self.current_token.value.parse::<i32>().unwrap()),
-      TokenKind::TRUE => Expression::Boolean(true),
-      TokenKind::FALSE => Expression::Boolean(false),
-      TokenKind::STRING => Expression::String(self.current_token.value.clone()),
-      TokenKind::LPAREN => self.parse_grouped_expression()?,
+      TokenKind::INT => self.parse_integer_literal()?,
+      TokenKind::FLOAT => self.parse_float_literal()?,
+      TokenKind::CURRENCY => self.parse_currency_literal()?,
+      TokenKind::TRUE => ExpressionKind::Boolean(true),
+      TokenKind::FALSE => ExpressionKind::Boolean(false),
+      TokenKind::STRING => ExpressionKind::String(self.current_token.value.clone()),
+      TokenKind::INTERPOLATION => self.parse_interpolated_string()?,
+      TokenKind::DATE => match Date::parse(&self.current_token.value) {
+        Some(d) => ExpressionKind::Date(d),
+        None => {
+          return Err(self.raise_error(
+            ParseErrorType::InvalidToken,
+            format!("Invalid date literal #{}#", self.current_token.value),
+          ))
+        }
+      },
+      TokenKind::LPAREN => self.parse_grouped_expression()?.kind,
       _ => {
         return Err(self.raise_error(
           ParseErrorType::InvalidToken,
@@ -651,28 +1372,226 @@ impl<'a> Parser<'a> {
       }
     };
     self.next_token();
+    let e = Expression::new(kind, self.span_from(&start));
     debug!("<<< parse_primary {}", e);
     Ok(e)
   }
 
+  /// Identifier of the current token without its type suffix, so `x$` and `x` are the same variable.
+  fn current_identifier(&self) -> String {
+    self
+      .current_token
+      .value
+      .trim_end_matches(['%', '&', '!', '#', '@', '$'])
+      .to_string()
+  }
+
+  /// Type given by the suffix of the current identifier: `%` and `&` are Integer,
+  /// `!` and `#` are Double, `@` is Currency and `$` is String.
+  fn current_identifier_type(&self) -> Option<RuntimeType> {
+    match self.current_token.value.chars().last() {
+      Some('%' | '&') => Some(RuntimeType::Integer),
+      Some('!' | '#') => Some(RuntimeType::Double),
+      Some('@') => Some(RuntimeType::Currency),
+      Some('$') => Some(RuntimeType::String),
+      _ => None,
+    }
+  }
+
+  /// Parses decimal, `&H` hexadecimal, `&O` octal and `&B` binary literals with an optional
+  /// `%` (Integer) or `&` (Long) suffix. Both are 32-bit, so radix literals are read as
+  /// two's complement, e.g. `&HFFFFFFFF` is -1.
+  /// A decimal literal without a suffix that is too large for an Integer is a BigInteger.
+  fn parse_integer_literal(&mut self) -> Result<ExpressionKind, ParseError> {
+    let literal = self.current_token.value.trim_end_matches(['%', '&']);
+    let parsed = if literal.starts_with('&') {
+      let radix = match &literal[1..2] {
+        "H" | "h" => 16,
+        "O" | "o" => 8,
+        _ => 2,
+      };
+      u32::from_str_radix(&literal[2..], radix).map(|n| n as i32)
+    } else {
+      literal.parse::<i32>()
+    };
+    match parsed {
+      Ok(n) => Ok(ExpressionKind::Integer(n)),
+      Err(_) if literal == self.current_token.value && !literal.starts_with('&') => Ok(
+        ExpressionKind::BigInteger(literal.parse::<BigInt>().unwrap()),
+      ),
+      Err(_) => Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Invalid integer literal {}", self.current_token.value),
+      )),
+    }
+  }
+
+  /// Parses Currency literals such as `19.99@`, which are read as decimals, not as Doubles.
+  fn parse_currency_literal(&mut self) -> Result<ExpressionKind, ParseError> {
+    let literal = self.current_token.value.trim_end_matches('@');
+    match Currency::parse(literal) {
+      Some(c) => Ok(ExpressionKind::Currency(c)),
+      None => Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Invalid currency literal {}", self.current_token.value),
+      )),
+    }
+  }
+
+  /// Parses floating-point literals. The `!` (Single) suffix rounds the value to single precision.
+  /// A literal too large for its type such as `1e400` is an error, not infinity.
+  fn parse_float_literal(&mut self) -> Result<ExpressionKind, ParseError> {
+    let literal = self
+      .current_token
+      .value
+      .trim_end_matches(['f', 'F', 'd', 'D', '#', '!']);
+    let is_single = self.current_token.value.ends_with('!');
+    let parsed = match literal.parse::<f64>() {
+      Ok(n) if is_single => Ok(n as f32 as f64),
+      r => r,
+    };
+    match parsed {
+      Ok(n) if n.is_infinite() => Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!(
+          "Floating-point literal {} is out of range",
+          self.current_token.value
+        ),
+      )),
+      Ok(n) => Ok(ExpressionKind::Double(n)),
+      Err(_) => Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!(
+          "Invalid floating-point literal {}",
+          self.current_token.value
+        ),
+      )),
+    }
+  }
+
+  /// Splits the text of `$"..."` into text and `{expression}` parts.
+  /// `""`, `{{` and `}}` are escapes for `"`, `{` and `}`.
+  fn parse_interpolated_string(&mut self) -> Result<ExpressionKind, ParseError> {
+    let raw = self.current_token.value.clone();
+    let mut parts: Vec<InterpolationPart> = vec![];
+    let mut text = String::new();
+    let mut chars = raw.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+      match c {
+        '"' | '{' | '}' if chars.peek().map(|p| p.1) == Some(c) => {
+          chars.next();
+          text.push(c);
+        }
+        '{' => {
+          let mut source = String::new();
+          let mut depth = 1;
+          let mut in_string = false;
+          while let Some((_, c)) = chars.next() {
+            if in_string {
+              if c == '"' && chars.peek().map(|p| p.1) == Some('"') {
+                source.push(c);
+                chars.next();
+              } else if c == '"' {
+                in_string = false;
+              }
+            } else {
+              match c {
+                '"' => in_string = true,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+              }
+              if depth == 0 {
+                break;
+              }
+            }
+            source.push(c);
+          }
+          if depth != 0 {
+            return Err(self.raise_error(
+              ParseErrorType::InvalidToken,
+              String::from("Expected '}' in interpolated string"),
+            ));
+          }
+          if !text.is_empty() {
+            parts.push(InterpolationPart::Text(std::mem::take(&mut text)));
+          }
+          let e = self.parse_embedded_expression(&source, &raw[..i + 1])?;
+          parts.push(InterpolationPart::Expression(e));
+        }
+        c => text.push(c),
+      }
+    }
+    if !text.is_empty() {
+      parts.push(InterpolationPart::Text(text));
+    }
+    Ok(ExpressionKind::InterpolatedString(parts))
+  }
+
+  /// Parses `source`, which follows `preceding` in the text of the current INTERPOLATION
+  /// token, so that the spans of the expression point into the original source.
+  fn parse_embedded_expression(
+    &mut self,
+    source: &str,
+    preceding: &str,
+  ) -> Result<Expression, ParseError> {
+    let (line, column) = match preceding.rfind('\n') {
+      Some(n) => (
+        self.current_token.line + preceding.matches('\n').count(),
+        preceding[n + 1..].chars().count(),
+      ),
+      // 2 is the length of `$"`.
+      None => (
+        self.current_token.line,
+        self.current_token.column + 2 + preceding.chars().count(),
+      ),
+    };
+    let i = InputStream::new_at(source, self.current_token.file_name.clone(), line, column);
+    let mut parser = Parser::new(Lexer::new(i));
+    let e = parser.parse_expression();
+    let message = match e {
+      Ok(e) if parser.current_token.kind == TokenKind::EOF => return Ok(e),
+      Ok(_) => format!("Expected '}}', but {}", parser.current_token.kind),
+      Err(e) => e.error_message,
+    };
+    Err(self.raise_error(
+      ParseErrorType::InvalidToken,
+      format!("{} in interpolated string {{{}}}", message, source),
+    ))
+  }
+
   fn binary_operation(
     &mut self,
     left: &Expression,
     operator: BinaryOperator,
     right: &Expression,
   ) -> Expression {
-    Expression::Binary {
+    let span = left.span.to(&right.span);
+    let kind = ExpressionKind::Binary {
       left: Box::new(left.clone()),
       operator,
       right: Box::new(right.clone()),
-    }
+    };
+    Expression::new(kind, span)
   }
 
-  fn unary_operation(&mut self, operator: UnaryOperator, expression: &Expression) -> Expression {
-    Expression::Unary {
-      operator,
-      expression: Box::new(expression.clone()),
-    }
+  /// Folds the negation of `2147483648` into the literal, so `-2147483648` is an Integer.
+  fn unary_operation(
+    &mut self,
+    start: &Span,
+    operator: UnaryOperator,
+    expression: &Expression,
+  ) -> Expression {
+    let kind = match (&operator, &expression.kind) {
+      (UnaryOperator::NEGATIVE, ExpressionKind::BigInteger(n)) if -n == BigInt::from(i32::MIN) => {
+        ExpressionKind::Integer(i32::MIN)
+      }
+      _ => ExpressionKind::Unary {
+        operator,
+        expression: Box::new(expression.clone()),
+      },
+    };
+    Expression::new(kind, start.to(&expression.span))
   }
 
   fn parse_grouped_expression(&mut self) -> Result<Expression, ParseError> {
@@ -689,6 +1608,23 @@ impl<'a> Parser<'a> {
     }
   }
 
+  /// A statement ends with a line break or with `:` as in `If x Then: Print(1): End If`.
+  fn is_end_of_statement(&self) -> bool {
+    matches!(self.current_token.kind, TokenKind::EOL | TokenKind::COLON)
+  }
+
+  /// Consumes the EOL or `:` which ends the first line of a block.
+  fn expect_end_of_statement(&mut self) -> Result<(), ParseError> {
+    if !self.is_end_of_statement() {
+      return Err(self.raise_error(
+        ParseErrorType::InvalidToken,
+        format!("Expected EOL, but {}", self.current_token.kind),
+      ));
+    }
+    self.next_token();
+    Ok(())
+  }
+
   fn raise_error(&mut self, error_type: ParseErrorType, error_message: String) -> ParseError {
     debug!(">>> raise_error: {},{}", error_type, error_message);
     ParseError {
@@ -700,9 +1636,272 @@ impl<'a> Parser<'a> {
     }
   }
 
+  /// The span of the current token, from which the span of a node starts.
+  fn current_span(&self) -> Span {
+    Span {
+      file_name: self.current_token.file_name.clone(),
+      line: self.current_token.line,
+      column: self.current_token.column,
+      end_line: self.current_token.end_line,
+      end_column: self.current_token.end_column,
+    }
+  }
+
+  /// The span from `start` to the end of the last consumed token.
+  fn span_from(&self, start: &Span) -> Span {
+    Span {
+      end_line: self.previous_end.0,
+      end_column: self.previous_end.1,
+      ..start.clone()
+    }
+  }
+
+  /// Placeholder for an expression which could not be parsed, so that parsing can go on.
+  fn missing_expression(&self) -> Expression {
+    Expression::new(ExpressionKind::Boolean(false), self.current_span())
+  }
+
   fn next_token(&mut self) {
+    self.previous_end = (self.current_token.end_line, self.current_token.end_column);
     self.current_token = self.next_token.clone();
     self.next_token = self.lexer.next_token();
     debug!("next_token: {}", self.current_token.kind);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_with_errors(source: &str) -> (Program, Vec<ParseError>) {
+    let lexer = Lexer::new(InputStream::new(source, String::from("test.bs")));
+    Parser::new(lexer).parse_program()
+  }
+
+  /// Parses `source`, which must have no errors.
+  fn parse(source: &str) -> Program {
+    let (program, errors) = parse_with_errors(source);
+    assert!(errors.is_empty(), "{:?}", errors);
+    program
+  }
+
+  /// The messages of the errors in `source`.
+  fn errors_of(source: &str) -> Vec<String> {
+    let (_, errors) = parse_with_errors(source);
+    errors.into_iter().map(|e| e.error_message).collect()
+  }
+
+  /// The initializers of the `Dim` statements in `source`.
+  fn declared_values(source: &str) -> Vec<ExpressionKind> {
+    parse(source)
+      .statements
+      .iter()
+      .map(|s| match &s.kind {
+        StatementKind::Declaration {
+          expression: Some(e),
+          ..
+        } => e.kind.clone(),
+        s => panic!("unexpected statement {:?}", s),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn float_literals_out_of_range_are_errors() {
+    assert!(errors_of("Dim x = 1e300").is_empty());
+    assert_eq!(
+      errors_of("Dim x = 1e400"),
+      vec!["Floating-point literal 1e400 is out of range"]
+    );
+    assert_eq!(
+      errors_of("Dim x = 1e39!"),
+      vec!["Floating-point literal 1e39! is out of range"]
+    );
+  }
+
+  #[test]
+  fn negated_minimum_integer_is_an_integer_literal() {
+    assert_eq!(
+      declared_values("Dim x = -2147483648"),
+      vec![ExpressionKind::Integer(i32::MIN)]
+    );
+  }
+
+  #[test]
+  fn single_line_if_may_have_an_empty_then_part() {
+    match &parse("If c Then Else x = 1").statements[0].kind {
+      StatementKind::IfStatement {
+        if_blocks,
+        else_statements,
+      } => {
+        assert!(if_blocks[0].1.is_empty());
+        assert_eq!(else_statements.len(), 1);
+      }
+      s => panic!("unexpected statement {:?}", s),
+    }
+  }
+
+  #[test]
+  fn floating_point_literal_values() {
+    let source = "Dim a = 3.0D\nDim b = 1.5e-3\nDim c = 0.1!\nDim d = 2e10";
+    assert_eq!(
+      declared_values(source),
+      vec![
+        ExpressionKind::Double(3.0),
+        ExpressionKind::Double(0.0015),
+        ExpressionKind::Double(0.1f32 as f64),
+        ExpressionKind::Double(2e10)
+      ]
+    );
+  }
+
+  #[test]
+  fn radix_literals_and_suffixes() {
+    let source = "Dim a = &HFF\nDim b = &O17%\nDim c = &B1010&\nDim d = &HFFFFFFFF\nDim e = 10&";
+    assert_eq!(
+      declared_values(source),
+      [255, 15, 10, -1, 10].map(ExpressionKind::Integer).to_vec()
+    );
+    assert_eq!(
+      errors_of("Dim a = &H100000000"),
+      vec!["Invalid integer literal &H100000000"]
+    );
+  }
+
+  #[test]
+  fn errors_are_recovered_at_the_end_of_the_statement() {
+    let source = "Dim x = \nPrint(1\nFunction F()\n  Dim y = )\nEnd Function\nDim z = 2\n";
+    let (program, errors) = parse_with_errors(source);
+    let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![0, 1, 3]);
+    assert!(program.statements.iter().any(|s| matches!(
+      &s.kind,
+      StatementKind::Declaration { identifier, .. } if identifier == "z"
+    )));
+  }
+
+  #[test]
+  fn blocks_with_broken_headers_are_skipped_to_their_end() {
+    let source = "For i = 1 To\n  Print(i)\nNext\nIf Then\nEnd If\nDim w = 1\n";
+    let (program, errors) = parse_with_errors(source);
+    let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![0, 3]);
+    assert!(matches!(
+      &program.statements.last().unwrap().kind,
+      StatementKind::Declaration { identifier, .. } if identifier == "w"
+    ));
+  }
+
+  #[test]
+  fn nodes_carry_their_spans() {
+    let program = parse("Dim a = 1\nDim b = 2 + a * _\n  (a - 1)");
+    let statement = &program.statements[1];
+    assert_eq!((statement.span.line, statement.span.column), (1, 0));
+    assert_eq!((statement.span.end_line, statement.span.end_column), (2, 9));
+    match &statement.kind {
+      StatementKind::Declaration {
+        expression: Some(e),
+        ..
+      } => {
+        let span = &e.span;
+        assert_eq!(
+          (span.line, span.column, span.end_line, span.end_column),
+          (1, 8, 2, 9)
+        );
+        assert_eq!(span.file_name, "test.bs");
+      }
+      s => panic!("unexpected statement {:?}", s),
+    }
+  }
+
+  #[test]
+  fn else_if_blocks_follow_the_if_block() {
+    let source =
+      "If a Then\n  x = 1\nElseIf b Then\nElse If c Then\n  x = 3\nElse\n  x = 4\nEnd If";
+    match &parse(source).statements[0].kind {
+      StatementKind::IfStatement {
+        if_blocks,
+        else_statements,
+      } => {
+        let sizes: Vec<usize> = if_blocks.iter().map(|(_, s)| s.len()).collect();
+        assert_eq!(sizes, vec![1, 0, 1]);
+        assert_eq!(else_statements.len(), 1);
+      }
+      s => panic!("unexpected statement {:?}", s),
+    }
+    assert!(!errors_of("If a Then\nElse\nElseIf b Then\nEnd If").is_empty());
+  }
+
+  #[test]
+  fn jumps_must_be_within_their_block() {
+    let source =
+      "Exit For\nFor i = 1 To 2\n  Exit Do\nNext\nFunction F()\n  Exit Sub\nEnd Function";
+    let messages = errors_of(source);
+    assert_eq!(
+      messages,
+      vec![
+        "Exit For is not within For",
+        "Exit Do is not within Do",
+        "Exit Sub is not within Sub"
+      ]
+    );
+  }
+
+  /// Parses `x = <source>` and writes the expression with every operation in parentheses.
+  fn grouping(source: &str) -> String {
+    fn group(e: &Expression) -> String {
+      match &e.kind {
+        ExpressionKind::Binary {
+          left,
+          operator,
+          right,
+        } => format!("({} {} {})", group(left), operator.symbol(), group(right)),
+        ExpressionKind::Unary {
+          operator: UnaryOperator::NOT,
+          expression,
+        } => format!("(Not {})", group(expression)),
+        ExpressionKind::Unary {
+          operator,
+          expression,
+        } => {
+          let sign = if *operator == UnaryOperator::NEGATIVE {
+            "-"
+          } else {
+            "+"
+          };
+          format!("({}{})", sign, group(expression))
+        }
+        ExpressionKind::Identifier(i) => i.clone(),
+        ExpressionKind::Integer(n) => n.to_string(),
+        e => panic!("unexpected expression {}", e),
+      }
+    }
+    match &parse(&format!("x = {}", source)).statements[0].kind {
+      StatementKind::Assignment { expression, .. } => group(expression),
+      s => panic!("unexpected statement {:?}", s),
+    }
+  }
+
+  #[test]
+  fn operators_bind_by_precedence() {
+    assert_eq!(grouping("1 + 2 * 3 ^ 2"), "(1 + (2 * (3 ^ 2)))");
+    assert_eq!(grouping("-2 ^ 2"), "(-(2 ^ 2))");
+    assert_eq!(grouping("a * b \\ c Mod d"), "(((a * b) \\ c) Mod d)");
+    assert_eq!(grouping("a & b + c = d"), "((a & (b + c)) = d)");
+    assert_eq!(
+      grouping("Not a = b And c Or d Xor e"),
+      "((((Not (a = b)) And c) Or d) Xor e)"
+    );
+    assert_eq!(grouping("a OrElse b AndAlso c"), "(a OrElse (b AndAlso c))");
+    assert_eq!(grouping("(1 + 2) * 3"), "((1 + 2) * 3)");
+  }
+
+  #[test]
+  fn binary_operators_are_left_associative() {
+    assert_eq!(grouping("a - b - c"), "((a - b) - c)");
+    assert_eq!(grouping("a / b * c"), "((a / b) * c)");
+    assert_eq!(grouping("a ^ b ^ c"), "((a ^ b) ^ c)");
+    assert_eq!(grouping("a & b & c"), "((a & b) & c)");
+    assert_eq!(grouping("- - a"), "(-(-a))");
+  }
+}
@@ -1,3 +1,6 @@
+use super::array::MAX_LENGTH;
+use super::ast::operator::BinaryOperator;
+use super::ast::span::Span;
 use super::object::{Object, RuntimeType, TypeOf};
 use std::fmt;
 
@@ -7,13 +10,50 @@ pub enum RuntimeError {
   UndefinedVariable(String),
   DuplicatedVariableDefinition(String),
   NonFunctionObjectIsInvoked(String, Object),
-  ArgumentMismatch(String),
+  /// The name of the function and which argument is wrong.
+  ArgumentMismatch(String, String),
   // UnsupportedFeature(String),
   UnknownMethod(String),
   TypeMismatch {
     expected: RuntimeType,
     actual: RuntimeType,
   },
+  NotIterable(RuntimeType),
+  /// `/`, `\` or `Mod` by zero, with the operator and the operands.
+  DivisionByZero(BinaryOperator, Object, Object),
+  /// A result which does not fit in its type, e.g. an Integer over 2147483647.
+  Overflow(BinaryOperator, Object, Object),
+  /// A value which does not fit in the type it is converted to, e.g. a BigInteger stored
+  /// into an Integer variable.
+  ConversionOverflow {
+    value: Object,
+    to: RuntimeType,
+  },
+  /// An array with more elements than `MAX_LENGTH`, with its bounds.
+  ArrayTooLarge(Vec<(i32, i32)>),
+  /// The index given to an array and the bounds of the array.
+  IndexOutOfRange {
+    index: Vec<i32>,
+    bounds: Vec<(i32, i32)>,
+  },
+  /// An error together with the span of the expression or statement which raised it.
+  Located {
+    error: Box<RuntimeError>,
+    span: Span,
+  },
+}
+
+impl RuntimeError {
+  /// Attaches `span` unless the error is already located at an inner node.
+  pub fn locate(self, span: &Span) -> RuntimeError {
+    match self {
+      RuntimeError::Located { .. } => self,
+      error => RuntimeError::Located {
+        error: Box::new(error),
+        span: span.clone(),
+      },
+    }
+  }
 }
 
 impl fmt::Display for RuntimeError {
@@ -27,9 +67,11 @@ impl fmt::Display for RuntimeError {
         "DuplicatedVariableDefinition, {} is already defined.",
         name
       ),
-      RuntimeError::ArgumentMismatch(name) => {
-        write!(f, "Arguments passed to function \"{}\" is invalid", name)
-      }
+      RuntimeError::ArgumentMismatch(name, reason) => write!(
+        f,
+        "Arguments passed to function \"{}\" is invalid, {}",
+        name, reason
+      ),
       RuntimeError::NonFunctionObjectIsInvoked(name, value) => write!(
         f,
         "NonFunctionObjectIsInvoked, {} is expected as Function, but actual is {}",
@@ -45,6 +87,50 @@ impl fmt::Display for RuntimeError {
         "Type mismatch, expected type is {}, but actual is {}.",
         expected, actual
       ),
+      RuntimeError::NotIterable(actual) => {
+        write!(f, "NotIterable, {} cannot be used in For Each.", actual)
+      }
+      RuntimeError::DivisionByZero(operator, l, r) => write!(
+        f,
+        "DivisionByZero, {} {} {} divides by zero.",
+        l,
+        operator.symbol(),
+        r
+      ),
+      RuntimeError::Overflow(operator, l, r) => write!(
+        f,
+        "Overflow, {} {} {} is out of range.",
+        l,
+        operator.symbol(),
+        r
+      ),
+      RuntimeError::ConversionOverflow { value, to } => {
+        write!(f, "Overflow, {} is out of the range of {}.", value, to)
+      }
+      RuntimeError::ArrayTooLarge(bounds) => write!(
+        f,
+        "ArrayTooLarge, ({}) has more than {} elements.",
+        join_bounds(bounds),
+        MAX_LENGTH
+      ),
+      RuntimeError::IndexOutOfRange { index, bounds } => {
+        let index: Vec<String> = index.iter().map(|i| i.to_string()).collect();
+        write!(
+          f,
+          "IndexOutOfRange, ({}) is out of ({}).",
+          index.join(", "),
+          join_bounds(bounds)
+        )
+      }
+      RuntimeError::Located { error, span } => write!(f, "{}\n{}", error, span),
     }
   }
 }
+
+fn join_bounds(bounds: &[(i32, i32)]) -> String {
+  let bounds: Vec<String> = bounds
+    .iter()
+    .map(|(lower, upper)| format!("{} To {}", lower, upper))
+    .collect();
+  bounds.join(", ")
+}
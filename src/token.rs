@@ -1,10 +1,36 @@
 use super::token_kind::TokenKind;
+use super::trivia::Trivia;
+use std::fmt;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Token {
   pub kind: TokenKind,
   pub value: String,
   pub file_name: String,
+  /// Location of the first character of the token.
   pub line: usize,
   pub column: usize,
+  /// Location just past the last character of the token.
+  pub end_line: usize,
+  pub end_column: usize,
+  /// Byte offsets of `text` in the source.
+  pub start: usize,
+  pub end: usize,
+  /// The token as written in the source, e.g. `"a""b"` for the string whose value is `a"b`.
+  pub text: String,
+  pub leading_trivia: Vec<Trivia>,
+  pub trailing_trivia: Vec<Trivia>,
+}
+
+impl fmt::Display for Token {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}..{} {} {:?}", self.start, self.end, self.kind, self.text)?;
+    for t in self.leading_trivia.iter() {
+      write!(f, " leading:{}{:?}", t.kind, t.text)?;
+    }
+    for t in self.trailing_trivia.iter() {
+      write!(f, " trailing:{}{:?}", t.kind, t.text)?;
+    }
+    Ok(())
+  }
 }
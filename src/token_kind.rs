@@ -1,14 +1,22 @@
 use std::fmt;
 
+/// Token kinds are named in upper case after the keywords and symbols they stand for.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd)]
 pub enum TokenKind {
   ILLEGAL,
   EOF,
   EOL,
   COMMA,
+  COLON,
+  COLONEQ,
   IDENT,
   INT,
+  FLOAT,
+  CURRENCY,
   STRING,
+  INTERPOLATION,
+  DATE,
   CONST,
   LPAREN,
   RPAREN,
@@ -16,20 +24,36 @@ pub enum TokenKind {
   MINUS,
   ASTERISK,
   SLASH,
+  BACKSLASH,
+  AMPERSAND,
   ASSIGN,
   PERCENT,
   HAT,
   MOD,
   AND,
+  ANDALSO,
   OR,
+  ORELSE,
   XOR,
   NOT,
   IF,
   THEN,
   ELSE,
+  ELSEIF,
   END,
+  SELECT,
+  CASE,
+  IS,
   FOR,
+  EACH,
+  IN,
+  STEP,
   NEXT,
+  DO,
+  LOOP,
+  WHILE,
+  UNTIL,
+  WEND,
   DIM,
   TO,
   // EQ,
@@ -41,7 +65,17 @@ pub enum TokenKind {
   TRUE,
   FALSE,
   RETURN,
+  EXIT,
+  CONTINUE,
   FUNCTION,
+  SUB,
+  BYVAL,
+  BYREF,
+  OPTIONAL,
+  PARAMARRAY,
+  AS,
+  REDIM,
+  PRESERVE,
 }
 
 impl fmt::Display for TokenKind {
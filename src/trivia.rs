@@ -0,0 +1,25 @@
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum TriviaKind {
+  Whitespace,
+  LineContinuation,
+  Comment,
+  DocComment,
+}
+impl fmt::Display for TriviaKind {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+/// Source text which is not significant to the parser but is kept on a token,
+/// e.g. spaces, ` _` line continuations, `' comment` and `''' doc comment`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trivia {
+  pub kind: TriviaKind,
+  pub text: String,
+  /// Byte offsets of `text` in the source.
+  pub start: usize,
+  pub end: usize,
+}
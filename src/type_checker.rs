@@ -0,0 +1,723 @@
+use super::ast::{
+  expression::Arguments, expression::Expression, expression::ExpressionKind,
+  expression::InterpolationPart, function::FunctionDeclaration, function::ProcedureKind,
+  operator::BinaryOperator, operator::UnaryOperator, program::Program, span::Span,
+  statement::ArrayBound, statement::CaseCondition, statement::LoopCondition, statement::Statement,
+  statement::StatementKind,
+};
+use super::object::RuntimeType;
+use super::parse_error::{ParseError, ParseErrorType};
+use log::debug;
+use std::collections::BTreeMap;
+
+/// The type of a variable and the type of its elements if it is an array.
+/// None is a Variant, whose type is known only at runtime.
+#[derive(Clone, Copy)]
+struct Declared {
+  type_name: Option<RuntimeType>,
+  element_type: Option<RuntimeType>,
+}
+
+impl Declared {
+  fn new(type_name: Option<RuntimeType>) -> Self {
+    Declared {
+      type_name,
+      element_type: None,
+    }
+  }
+
+  fn array(element_type: Option<RuntimeType>) -> Self {
+    Declared {
+      type_name: Some(RuntimeType::Array),
+      element_type,
+    }
+  }
+}
+
+/// Variables declared in a procedure, keyed by the lowercased name.
+type Scope = BTreeMap<String, Declared>;
+
+/// Reports type mismatches before the program runs.
+/// Only types known statically are checked, i.e. literals, variables declared with `As` or
+/// a type suffix and the results of operators and functions on them. Anything involving a
+/// Variant is left to the executor.
+pub struct TypeChecker<'a> {
+  functions: BTreeMap<String, &'a FunctionDeclaration>,
+  /// The top level scope and the scope of the procedure being checked.
+  scopes: Vec<Scope>,
+  /// Return type of the Function being checked.
+  return_type: Option<RuntimeType>,
+  errors: Vec<ParseError>,
+}
+
+impl<'a> TypeChecker<'a> {
+  pub fn new() -> Self {
+    TypeChecker {
+      functions: BTreeMap::new(),
+      scopes: vec![Scope::new()],
+      return_type: None,
+      errors: vec![],
+    }
+  }
+
+  pub fn check(mut self, program: &'a Program) -> Vec<ParseError> {
+    debug!(">>> check");
+    for f in program.functions.iter() {
+      self.functions.insert(f.identifier.to_lowercase(), f);
+    }
+    self.check_statements(&program.statements);
+    for f in program.functions.iter() {
+      self.check_function(f);
+    }
+    debug!("<<< check: {} error(s)", self.errors.len());
+    self.errors
+  }
+
+  fn check_function(&mut self, f: &FunctionDeclaration) {
+    let mut scope = Scope::new();
+    for p in f.parameters.iter() {
+      let declared = if p.param_array {
+        Declared::array(p.type_name)
+      } else {
+        Declared::new(p.type_name)
+      };
+      scope.insert(p.name.to_lowercase(), declared);
+    }
+    scope.insert(String::from("ret"), Declared::new(f.return_type));
+    self.scopes.push(scope);
+    self.return_type = f.return_type;
+    self.check_statements(&f.statements);
+    self.return_type = None;
+    self.scopes.pop();
+  }
+
+  fn check_statements(&mut self, statements: &[Statement]) {
+    for s in statements {
+      self.check_statement(s);
+    }
+  }
+
+  fn check_statement(&mut self, statement: &Statement) {
+    match &statement.kind {
+      StatementKind::Declaration {
+        identifier,
+        type_name,
+        dimensions,
+        expression,
+      } => {
+        if let Some(e) = expression {
+          let actual = self.type_of(e);
+          self.expect(type_name, &actual, &e.span);
+        }
+        let declared = match dimensions {
+          Some(d) => {
+            self.check_array_bounds(d);
+            Declared::array(*type_name)
+          }
+          None => Declared::new(*type_name),
+        };
+        let len = self.scopes.len();
+        self.scopes[len - 1].insert(identifier.to_lowercase(), declared);
+      }
+      StatementKind::Assignment {
+        identifier,
+        expression,
+      } => {
+        let actual = self.type_of(expression);
+        if let Some(declared) = self.variable_type(identifier) {
+          self.expect(&declared.type_name, &actual, &expression.span);
+        }
+      }
+      StatementKind::IndexedAssignment {
+        identifier,
+        indices,
+        expression,
+      } => {
+        let element_type = self.check_array_access(identifier, indices);
+        let actual = self.type_of(expression);
+        self.expect(&element_type, &actual, &expression.span);
+      }
+      StatementKind::ReDimStatement {
+        identifier,
+        dimensions,
+        ..
+      } => {
+        self.check_array_bounds(dimensions);
+        if let Some(declared) = self.variable_type(identifier) {
+          self.expect(
+            &declared.type_name,
+            &Some(RuntimeType::Array),
+            &statement.span,
+          );
+        }
+      }
+      StatementKind::MethodInvocation {
+        identifier,
+        arguments,
+      } => {
+        self.check_call(identifier, arguments);
+      }
+      StatementKind::IfStatement {
+        if_blocks,
+        else_statements,
+      } => {
+        for (condition, statements) in if_blocks {
+          self.expect_condition(condition);
+          self.check_statements(statements);
+        }
+        self.check_statements(else_statements);
+      }
+      StatementKind::SelectStatement {
+        expression,
+        case_blocks,
+        else_statements,
+      } => {
+        let value = self.type_of(expression);
+        for (conditions, statements) in case_blocks {
+          for c in conditions {
+            match c {
+              CaseCondition::Value(e) => self.expect_comparable(&value, e),
+              CaseCondition::Range(from, to) => {
+                self.expect_comparable(&value, from);
+                self.expect_comparable(&value, to);
+              }
+              CaseCondition::Comparison(_, e) => self.expect_comparable(&value, e),
+            }
+          }
+          self.check_statements(statements);
+        }
+        self.check_statements(else_statements);
+      }
+      StatementKind::ForStatement {
+        loop_counter_from,
+        loop_counter_to,
+        loop_counter_step,
+        block,
+        ..
+      } => {
+        self.expect_number(loop_counter_from);
+        self.expect_number(loop_counter_to);
+        if let Some(step) = loop_counter_step {
+          self.expect_number(step);
+        }
+        self.check_statements(block);
+      }
+      StatementKind::ForEachStatement {
+        collection, block, ..
+      } => {
+        match self.type_of(collection) {
+          Some(RuntimeType::String | RuntimeType::Array) | None => {}
+          Some(t) => self.error(
+            &collection.span,
+            format!("{} cannot be used in For Each.", t),
+          ),
+        }
+        self.check_statements(block);
+      }
+      StatementKind::DoStatement {
+        pre_condition,
+        block,
+        post_condition,
+      } => {
+        for c in pre_condition.iter().chain(post_condition.iter()) {
+          match c {
+            LoopCondition::While(e) | LoopCondition::Until(e) => self.expect_condition(e),
+          }
+        }
+        self.check_statements(block);
+      }
+      StatementKind::ReturnStatement {
+        expression: Some(e),
+      } => {
+        let actual = self.type_of(e);
+        let expected = self.return_type;
+        self.expect(&expected, &actual, &e.span);
+      }
+      StatementKind::ReturnStatement { expression: None }
+      | StatementKind::ExitStatement(_)
+      | StatementKind::ContinueStatement(_)
+      | StatementKind::Empty => {}
+    }
+  }
+
+  /// Static type of `expression`, or None if it is known only at runtime.
+  /// A mismatch inside `expression` is reported and the expression is treated as a Variant
+  /// so that it is reported only once.
+  fn type_of(&mut self, expression: &Expression) -> Option<RuntimeType> {
+    match &expression.kind {
+      ExpressionKind::Boolean(_) => Some(RuntimeType::Boolean),
+      ExpressionKind::Integer(_) => Some(RuntimeType::Integer),
+      ExpressionKind::BigInteger(_) => Some(RuntimeType::BigInteger),
+      ExpressionKind::Double(_) => Some(RuntimeType::Double),
+      ExpressionKind::Currency(_) => Some(RuntimeType::Currency),
+      ExpressionKind::Date(_) => Some(RuntimeType::Date),
+      ExpressionKind::String(_) => Some(RuntimeType::String),
+      ExpressionKind::InterpolatedString(parts) => {
+        for p in parts {
+          if let InterpolationPart::Expression(e) = p {
+            if let Some(t @ (RuntimeType::Array | RuntimeType::Function)) = self.type_of(e) {
+              self.error(&e.span, format!("{} cannot be converted to String.", t));
+            }
+          }
+        }
+        Some(RuntimeType::String)
+      }
+      ExpressionKind::Identifier(name) => match self.variable_type(name) {
+        Some(declared) => declared.type_name,
+        None if name.to_lowercase() == "now" => Some(RuntimeType::Date),
+        None => None,
+      },
+      ExpressionKind::FunctionInvocation {
+        identifier,
+        arguments,
+      } => self.check_call(identifier, arguments),
+      ExpressionKind::Unary {
+        operator,
+        expression: operand,
+      } => {
+        let t = self.type_of(operand);
+        match (operator, t) {
+          (UnaryOperator::NOT, Some(RuntimeType::Boolean) | None) => Some(RuntimeType::Boolean),
+          (UnaryOperator::NEGATIVE | UnaryOperator::POSITIVE, None) => None,
+          (
+            UnaryOperator::NEGATIVE | UnaryOperator::POSITIVE,
+            Some(
+              t @ (RuntimeType::Integer
+              | RuntimeType::BigInteger
+              | RuntimeType::Double
+              | RuntimeType::Currency),
+            ),
+          ) => Some(t),
+          (_, Some(t)) => {
+            self.error(
+              &expression.span,
+              format!("Operator {} cannot be applied to {}.", operator, t),
+            );
+            None
+          }
+        }
+      }
+      ExpressionKind::Binary {
+        left,
+        operator,
+        right,
+      } => {
+        let l = self.type_of(left);
+        let r = self.type_of(right);
+        match (l, r) {
+          (Some(l), Some(r)) => match Self::binary_type(&l, operator, &r) {
+            Some(t) => Some(t),
+            None => {
+              self.error(
+                &expression.span,
+                format!(
+                  "Operator {} cannot be applied to {} and {}.",
+                  operator, l, r
+                ),
+              );
+              None
+            }
+          },
+          _ => Self::fixed_result_type(operator),
+        }
+      }
+    }
+  }
+
+  /// Result type of a binary operator as the executor evaluates it, or None if it fails.
+  fn binary_type(
+    l: &RuntimeType,
+    operator: &BinaryOperator,
+    r: &RuntimeType,
+  ) -> Option<RuntimeType> {
+    use BinaryOperator::*;
+    use RuntimeType::*;
+    let is_arithmetic = matches!(operator, ADD | SUB | MUL | MOD);
+    let is_comparison = matches!(operator, EQ | NE | GT | GE | LT | LE);
+    let is_text = |t: &RuntimeType| !matches!(t, Array | Function);
+    let is_number = |t: &RuntimeType| matches!(t, Integer | BigInteger | Double | Currency);
+    match (l, r) {
+      (l, r) if *operator == CONCAT && is_text(l) && is_text(r) => Some(String),
+      (String, String) if *operator == ADD => Some(String),
+      (String, String) if is_comparison => Some(Boolean),
+      // An Integer result out of range is a BigInteger at runtime, which narrows back when it
+      // is stored into an Integer as `RuntimeType::accepts` allows.
+      (Integer, Integer) if is_arithmetic || *operator == INTDIV => Some(Integer),
+      (Integer | BigInteger, Integer | BigInteger) if is_arithmetic || *operator == INTDIV => {
+        Some(BigInteger)
+      }
+      (Currency, Integer | Currency) | (Integer, Currency) if is_arithmetic || *operator == DIV => {
+        Some(Currency)
+      }
+      (l, r) if is_number(l) && is_number(r) => match operator {
+        INTDIV | MOD => Some(Integer),
+        DIV | EXPOTENTIAL => Some(Double),
+        _ if is_arithmetic => Some(Double),
+        _ if is_comparison => Some(Boolean),
+        _ => None,
+      },
+      (Date, Date) if *operator == SUB => Some(Double),
+      (Date, Date) if is_comparison => Some(Boolean),
+      (Date, Integer) if matches!(operator, ADD | SUB) => Some(Date),
+      (Integer, Date) if *operator == ADD => Some(Date),
+      (Boolean, Boolean) if matches!(operator, AND | ANDALSO | OR | ORELSE | XOR) => Some(Boolean),
+      _ => None,
+    }
+  }
+
+  /// Result type of an operator which does not depend on the types of its operands, e.g.
+  /// `&` always makes a String. Used when an operand is a Variant.
+  fn fixed_result_type(operator: &BinaryOperator) -> Option<RuntimeType> {
+    use BinaryOperator::*;
+    match operator {
+      EQ | NE | GT | GE | LT | LE | AND | ANDALSO | OR | ORELSE | XOR => Some(RuntimeType::Boolean),
+      CONCAT => Some(RuntimeType::String),
+      INTDIV => Some(RuntimeType::Integer),
+      EXPOTENTIAL => Some(RuntimeType::Double),
+      // `/` is left out as it makes a Currency if both operands are Currency or Integer.
+      _ => None,
+    }
+  }
+
+  /// `Round(number, digits)` returns the type of the number it rounds.
+  fn check_round(&mut self, arguments: &Arguments) -> Option<RuntimeType> {
+    let mut number = None;
+    for (i, a) in arguments.positional.iter().enumerate() {
+      let actual = self.type_of(a);
+      match i {
+        0 => number = actual,
+        _ => self.expect(&Some(RuntimeType::Integer), &actual, &a.span),
+      }
+    }
+    for (_, a) in arguments.named.iter() {
+      self.type_of(a);
+    }
+    match (number, arguments.positional.first()) {
+      (
+        Some(RuntimeType::Integer)
+        | Some(RuntimeType::BigInteger)
+        | Some(RuntimeType::Double)
+        | Some(RuntimeType::Currency),
+        _,
+      ) => number,
+      (Some(_), Some(a)) => {
+        self.expect(&Some(RuntimeType::Double), &number, &a.span);
+        None
+      }
+      _ => None,
+    }
+  }
+
+  /// Checks the arguments of a call and returns the type of its result.
+  fn check_call(&mut self, identifier: &str, arguments: &Arguments) -> Option<RuntimeType> {
+    use RuntimeType::*;
+    // A user procedure shadows the builtin of the same name.
+    let name = identifier.to_lowercase();
+    let builtin = if self.functions.contains_key(&name) {
+      ""
+    } else {
+      name.as_str()
+    };
+    if builtin == "round" {
+      return self.check_round(arguments);
+    }
+    let builtin: Option<(&[RuntimeType], Option<RuntimeType>)> = match builtin {
+      "print" => Some((&[], None)),
+      "now" => Some((&[], Some(Date))),
+      "dateadd" => Some((&[String, Integer, Date], Some(Date))),
+      "datediff" => Some((&[String, Date, Date], Some(Integer))),
+      "year" | "month" | "day" => Some((&[Date], Some(Integer))),
+      "lbound" | "ubound" => Some((&[Array, Integer], Some(Integer))),
+      "cbigint" => Some((&[], Some(BigInteger))),
+      "ccur" => Some((&[], Some(Currency))),
+      _ => None,
+    };
+    if let Some((parameters, result)) = builtin {
+      for (i, a) in arguments.positional.iter().enumerate() {
+        let actual = self.type_of(a);
+        if let Some(expected) = parameters.get(i) {
+          self.expect(&Some(*expected), &actual, &a.span);
+        }
+      }
+      for (_, a) in arguments.named.iter() {
+        self.type_of(a);
+      }
+      return result;
+    }
+
+    if let Some(declared) = self.variable_type(identifier) {
+      if declared.type_name == Some(Array) && arguments.named.is_empty() {
+        return self.check_array_access(identifier, &arguments.positional);
+      }
+    }
+    let f = match self.functions.get(&identifier.to_lowercase()) {
+      Some(f) => *f,
+      None => {
+        for a in arguments.positional.iter() {
+          self.type_of(a);
+        }
+        for (_, a) in arguments.named.iter() {
+          self.type_of(a);
+        }
+        return None;
+      }
+    };
+    // The number of arguments is checked by the executor, so extra arguments are only
+    // checked for their own mismatches here.
+    let fixed = f.parameters.iter().take_while(|p| !p.param_array).count();
+    for (i, a) in arguments.positional.iter().enumerate() {
+      let actual = self.type_of(a);
+      if let Some(p) = f.parameters.get(i.min(fixed)) {
+        self.expect(&p.type_name, &actual, &a.span);
+      }
+    }
+    for (name, a) in arguments.named.iter() {
+      let actual = self.type_of(a);
+      let parameter = f.parameters[..fixed]
+        .iter()
+        .find(|p| p.name.to_lowercase() == name.to_lowercase());
+      if let Some(p) = parameter {
+        self.expect(&p.type_name, &actual, &a.span);
+      }
+    }
+    match f.kind {
+      ProcedureKind::Function => f.return_type,
+      ProcedureKind::Sub => None,
+    }
+  }
+
+  /// Checks the indices of `a(i, j)` and returns the type of the elements.
+  fn check_array_access(
+    &mut self,
+    identifier: &str,
+    indices: &[Expression],
+  ) -> Option<RuntimeType> {
+    for i in indices {
+      self.expect_index(i);
+    }
+    match self.variable_type(identifier) {
+      Some(declared) if declared.type_name == Some(RuntimeType::Array) => declared.element_type,
+      _ => None,
+    }
+  }
+
+  fn check_array_bounds(&mut self, dimensions: &[ArrayBound]) {
+    for d in dimensions {
+      if let Some(lower) = &d.lower {
+        self.expect_index(lower);
+      }
+      self.expect_index(&d.upper);
+    }
+  }
+
+  /// Declared type of a variable. None if it is not declared, e.g. a loop counter.
+  fn variable_type(&self, name: &str) -> Option<Declared> {
+    let key = name.to_lowercase();
+    self.scopes.iter().rev().find_map(|s| s.get(&key).copied())
+  }
+
+  fn expect(&mut self, expected: &Option<RuntimeType>, actual: &Option<RuntimeType>, span: &Span) {
+    if let (Some(expected), Some(actual)) = (expected, actual) {
+      if !expected.accepts(actual) {
+        self.error(
+          span,
+          format!(
+            "Type mismatch, expected type is {}, but actual is {}.",
+            expected, actual
+          ),
+        );
+      }
+    }
+  }
+
+  fn expect_condition(&mut self, condition: &Expression) {
+    let actual = self.type_of(condition);
+    self.expect(&Some(RuntimeType::Boolean), &actual, &condition.span);
+  }
+
+  fn expect_index(&mut self, expression: &Expression) {
+    let actual = self.type_of(expression);
+    self.expect(&Some(RuntimeType::Integer), &actual, &expression.span);
+  }
+
+  /// Checks a bound or the step of `For`, which may be of any numeric type.
+  fn expect_number(&mut self, expression: &Expression) {
+    match self.type_of(expression) {
+      Some(
+        RuntimeType::Integer
+        | RuntimeType::BigInteger
+        | RuntimeType::Double
+        | RuntimeType::Currency,
+      )
+      | None => {}
+      Some(actual) => self.error(
+        &expression.span,
+        format!(
+          "Type mismatch, expected type is a number, but actual is {}.",
+          actual
+        ),
+      ),
+    }
+  }
+
+  /// Checks that a `Case` value can be compared with the value of `Select Case`.
+  fn expect_comparable(&mut self, value: &Option<RuntimeType>, expression: &Expression) {
+    let actual = self.type_of(expression);
+    if let (Some(value), Some(actual)) = (value, actual) {
+      if Self::binary_type(value, &BinaryOperator::EQ, &actual).is_none() {
+        self.error(
+          &expression.span,
+          format!("Case of {} cannot be compared with {}.", actual, value),
+        );
+      }
+    }
+  }
+
+  fn error(&mut self, span: &Span, message: String) {
+    self.errors.push(ParseError {
+      error_type: ParseErrorType::TypeMismatch,
+      error_message: message,
+      file_name: span.file_name.clone(),
+      line: span.line,
+      column: span.column,
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::input_stream::InputStream;
+  use super::super::lexer::Lexer;
+  use super::super::parser::Parser;
+  use super::*;
+
+  /// Messages of the type mismatches reported for `source`.
+  fn check(source: &str) -> Vec<String> {
+    let lexer = Lexer::new(InputStream::new(source, String::from("test.bs")));
+    let (program, errors) = Parser::new(lexer).parse_program();
+    assert!(errors.is_empty(), "{:?}", errors);
+    TypeChecker::new()
+      .check(&program)
+      .into_iter()
+      .map(|e| e.error_message)
+      .collect()
+  }
+
+  #[test]
+  fn for_accepts_every_numeric_type() {
+    assert!(check("For c = 1@ To 2@ Step 0.5@\nNext").is_empty());
+    assert!(check("For d = 0.5 To 2 Step 0.5\nNext").is_empty());
+    assert!(check("For n = CBigInt(1) To 3\nNext").is_empty());
+    assert_eq!(
+      check("For s = \"a\" To 2\nNext"),
+      vec!["Type mismatch, expected type is a number, but actual is String."]
+    );
+  }
+
+  #[test]
+  fn interpolated_array_is_rejected() {
+    assert_eq!(
+      check("Dim a(2)\nDim s = $\"{a}\""),
+      vec!["Array cannot be converted to String."]
+    );
+  }
+
+  #[test]
+  fn minimum_integer_literal_is_an_integer() {
+    assert!(check("Dim x As Integer = -2147483648").is_empty());
+  }
+
+  #[test]
+  fn strings_are_comparable() {
+    let source = "Dim s As String = \"a\"
+Dim b As Boolean = s < \"b\"
+Select Case s
+  Case \"a\", \"b\" To \"c\", Is <> \"d\"
+End Select";
+    assert!(check(source).is_empty());
+    assert_eq!(
+      check("Select Case \"a\"\n  Case 1\nEnd Select"),
+      vec!["Case of Integer cannot be compared with String."]
+    );
+  }
+
+  #[test]
+  fn doubles_are_type_checked() {
+    assert!(check("Dim d As Double = 1\nd = d * 2.5").is_empty());
+    assert_eq!(
+      check("Dim x As Integer = 1.5\nDim s As String = 2.5 * 2"),
+      vec![
+        "Type mismatch, expected type is Integer, but actual is Double.",
+        "Type mismatch, expected type is String, but actual is Double."
+      ]
+    );
+  }
+
+  #[test]
+  fn identifier_suffixes_declare_types() {
+    let source = "Dim n% = 1\nDim l& = 2\nDim s! = 1.5\nDim d# = 2.5\nDim t$ = \"a\"\nDim c@ = 1@";
+    assert!(check(source).is_empty());
+    assert_eq!(
+      check("Dim t$ = 1"),
+      vec!["Type mismatch, expected type is String, but actual is Integer."]
+    );
+  }
+
+  #[test]
+  fn loop_conditions_must_be_boolean() {
+    let source = "Dim n = 1\nDo While 1\nLoop\nDo\nLoop Until \"x\"\nWhile n\nWend";
+    assert_eq!(
+      check(source),
+      vec![
+        "Type mismatch, expected type is Boolean, but actual is Integer.",
+        "Type mismatch, expected type is Boolean, but actual is String."
+      ]
+    );
+  }
+
+  #[test]
+  fn for_each_needs_an_iterable() {
+    assert!(check("Dim a(2)\nFor Each x In a\nNext\nFor Each c In \"ab\"\nNext").is_empty());
+    assert_eq!(
+      check("For Each c In 5\nNext"),
+      vec!["Integer cannot be used in For Each."]
+    );
+  }
+
+  #[test]
+  fn typed_declarations_and_functions() {
+    let source = "Function Shout(s As String, n As Integer) As String
+  Return s & n
+End Function
+Dim ok As Boolean = Shout(\"a\", 1) = \"a1\"
+Dim x As Integer
+x = \"text\"
+Dim b As Boolean = Shout(1, \"n\")
+Function Bad() As Integer
+  Return \"no\"
+End Function";
+    assert_eq!(
+      check(source),
+      vec![
+        // x = "text"
+        "Type mismatch, expected type is Integer, but actual is String.",
+        // Shout(1, "n")
+        "Type mismatch, expected type is String, but actual is Integer.",
+        "Type mismatch, expected type is Integer, but actual is String.",
+        // Dim b As Boolean = Shout(..)
+        "Type mismatch, expected type is Boolean, but actual is String.",
+        // Return "no"
+        "Type mismatch, expected type is Integer, but actual is String."
+      ]
+    );
+  }
+
+  #[test]
+  fn short_circuit_operators_take_booleans() {
+    assert!(check("Dim b As Boolean = True AndAlso False OrElse True").is_empty());
+    assert_eq!(
+      check("Dim b = 1 AndAlso True"),
+      vec!["Operator ANDALSO cannot be applied to Integer and Boolean."]
+    );
+  }
+}